@@ -4,15 +4,106 @@ use crate::auxiliary::zero;
 use crate::pages::{Pages, GuardedAlloc, Protection};
 
 use std::alloc::{Allocator, AllocError, Layout, handle_alloc_error};
+use std::fmt;
 use std::intrinsics::{likely, unlikely};
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Allocator for sensitive information
-pub struct Sensitive;
+///
+/// Allocations may be accounted to a [`Tag`] by creating the allocator with [`tagged`](Self::tagged).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sensitive {
+	tag: Option<&'static Tag>,
+}
+
+/// Untagged [`Sensitive`] allocator
+#[allow(non_upper_case_globals)]
+pub const Sensitive: Sensitive = Sensitive { tag: None };
+
+/// Allocation tag with quota
+///
+/// Tracks the number of bytes allocated by [`Sensitive`] allocators [tagged](Sensitive::tagged) with it. Allocations
+/// that would exceed the quota fail.
+pub struct Tag {
+	name: &'static str,
+	quota: usize,
+	usage: AtomicUsize,
+}
 
 impl Sensitive {
 	/// Number of guard pages
 	pub(crate) const GUARD_PAGES: usize = 1;
+
+	#[inline]
+	pub const fn tagged(tag: &'static Tag) -> Self {
+		Self { tag: Some(tag) }
+	}
+
+	#[must_use] #[inline]
+	pub const fn tag(&self) -> Option<&'static Tag> {
+		self.tag
+	}
+
+	fn charge(&self, size: usize) -> Result<(), AllocError> {
+		match self.tag {
+			Some(tag) => tag.charge(size),
+			None => Ok(()),
+		}
+	}
+
+	fn refund(&self, size: usize) {
+		if let Some(tag) = self.tag {
+			tag.refund(size);
+		}
+	}
+}
+
+impl Tag {
+	#[must_use]
+	pub const fn new(name: &'static str, quota: usize) -> Self {
+		Self { name, quota, usage: AtomicUsize::new(0) }
+	}
+
+	#[must_use]
+	pub const fn unlimited(name: &'static str) -> Self {
+		Self::new(name, usize::MAX)
+	}
+
+	#[must_use] #[inline]
+	pub const fn name(&self) -> &'static str {
+		self.name
+	}
+
+	#[must_use] #[inline]
+	pub const fn quota(&self) -> usize {
+		self.quota
+	}
+
+	/// Number of bytes currently allocated, rounded up to whole pages
+	#[must_use] #[inline]
+	pub fn usage(&self) -> usize {
+		self.usage.load(Ordering::Relaxed)
+	}
+
+	fn charge(&self, size: usize) -> Result<(), AllocError> {
+		self.usage.fetch_update(Ordering::AcqRel, Ordering::Acquire, |usage| {
+			usage.checked_add(size).filter(|usage| *usage <= self.quota)
+		}).map(|_| ()).map_err(|_| AllocError)
+	}
+
+	fn refund(&self, size: usize) {
+		let usage = self.usage.fetch_sub(size, Ordering::AcqRel);
+
+		// Panic on underflow
+		debug_assert!(usage >= size);
+	}
+}
+
+impl fmt::Debug for Tag {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(fmt, "Tag({}, {}/{})", self.name, self.usage(), self.quota)
+	}
 }
 
 unsafe impl Allocator for Sensitive {
@@ -22,11 +113,22 @@ unsafe impl Allocator for Sensitive {
 			return Err(AllocError);
 		}
 
-		let alloc = GuardedAlloc::<{ Self::GUARD_PAGES }>::new(layout.size(), Protection::ReadWrite).map_err(|_| AllocError)?;
+		// Enforce quota
+		let size = GuardedAlloc::<{ Self::GUARD_PAGES }>::inner_size(layout.size());
+		self.charge(size)?;
+
+		let alloc = match GuardedAlloc::<{ Self::GUARD_PAGES }>::new(layout.size(), Protection::ReadWrite) {
+			Ok(alloc) => alloc,
+			Err(_) => {
+				self.refund(size);
+				return Err(AllocError);
+			}
+		};
 
 		if likely(!alloc.inner().is_empty()) {
 			// Attempt to lock memory
 			if alloc.inner().lock().is_err() && cfg!(feature = "force-mlock") {
+				self.refund(size);
 				return Err(AllocError);
 			}
 		}
@@ -58,6 +160,8 @@ unsafe impl Allocator for Sensitive {
 				handle_alloc_error(layout);
 			}
 		}
+
+		self.refund(GuardedAlloc::<{ Self::GUARD_PAGES }>::inner_size(layout.size()));
 	}
 
 	unsafe fn shrink(&self, ptr: NonNull<u8>, old: Layout, new: Layout) -> Result<NonNull<[u8]>, AllocError> {
@@ -90,7 +194,9 @@ unsafe impl Allocator for Sensitive {
 			alloc = match alloc.shrink(GuardedAlloc::<{ Self::GUARD_PAGES }>::outer_size(inner_new)) {
 				Ok(alloc) => alloc,
 				Err(_) => handle_alloc_error(new)
-			}
+			};
+
+			self.refund(diff);
 		}
 
 		Ok(alloc.into_slice())
@@ -234,6 +340,33 @@ mod tests {
 		unsafe { Sensitive.deallocate(alloc_1.cast::<u8>(), layout_1); }
 	}
 
+	#[test]
+	fn tagged() {
+		static TAG: Tag = Tag::new("test", 16 * 65536);
+
+		let alloc = Sensitive::tagged(&TAG);
+		let layout = Layout::from_size_align(65536, 1).unwrap();
+		let mut allocs = std::vec::Vec::new();
+
+		while let Ok(ptr) = alloc.allocate(layout) {
+			allocs.push(ptr);
+		}
+
+		assert!(!allocs.is_empty());
+		assert!(TAG.usage() <= TAG.quota());
+		assert_eq!(TAG.usage(), allocs.len() * GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::inner_size(65536));
+
+		// Untagged allocations are not affected
+		let untagged = Sensitive.allocate(layout).unwrap();
+		unsafe { Sensitive.deallocate(untagged.cast::<u8>(), layout); }
+
+		for ptr in allocs {
+			unsafe { alloc.deallocate(ptr.cast::<u8>(), layout); }
+		}
+
+		assert_eq!(TAG.usage(), 0);
+	}
+
 	#[test]
 	fn vec_seq() {
 		const LIMIT: usize = 1048576;
@@ -0,0 +1,186 @@
+//! Scoped arena allocator
+
+use crate::auxiliary::zero;
+use crate::pages::{Pages, GuardedAlloc, Protection};
+use crate::alloc::Sensitive;
+
+use std::alloc::{Allocator, AllocError, Layout};
+use std::cell::Cell;
+use std::intrinsics::{likely, unlikely};
+use std::io::Error;
+use std::ptr::NonNull;
+
+/// Arena allocator for sensitive information
+///
+/// All values are bump‐allocated from a single guarded and locked memory region. Memory is zeroed on
+/// [`reset`](Self::reset) and when the arena is dropped.
+#[must_use]
+#[derive(Debug)]
+pub struct Arena {
+	alloc: GuardedAlloc<{ Sensitive::GUARD_PAGES }>,
+	offset: Cell<usize>,
+}
+
+impl Arena {
+	#[allow(clippy::missing_errors_doc)]
+	pub fn new(capacity: usize) -> Result<Self, Error> {
+		let alloc = GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::new(capacity, Protection::ReadWrite)?;
+
+		if likely(!alloc.inner().is_empty()) {
+			// Attempt to lock memory
+			if let Err(err) = alloc.inner().lock() {
+				if cfg!(feature = "force-mlock") {
+					return Err(err);
+				}
+			}
+		}
+
+		Ok(Self { alloc, offset: Cell::new(0) })
+	}
+
+	#[must_use] #[inline]
+	pub fn capacity(&self) -> usize {
+		self.alloc.inner().size()
+	}
+
+	#[must_use] #[inline]
+	pub fn used(&self) -> usize {
+		self.offset.get()
+	}
+
+	#[must_use] #[inline]
+	pub fn available(&self) -> usize {
+		self.capacity() - self.used()
+	}
+
+	/// Zero all memory and make it available for reuse
+	pub fn reset(&mut self) {
+		unsafe { zero(self.alloc.inner().as_ptr::<u8>(), self.offset.get()); }
+		self.offset.set(0);
+	}
+}
+
+unsafe impl Allocator for Arena {
+	fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+		// Refuse allocation if alignment requirement exceeds page size
+		if unlikely(layout.align() >= Pages::granularity()) {
+			return Err(AllocError);
+		}
+
+		let start = self.offset.get().next_multiple_of(layout.align());
+		let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+
+		if unlikely(end > self.capacity()) {
+			return Err(AllocError);
+		}
+
+		self.offset.set(end);
+
+		let ptr = unsafe { self.alloc.inner().as_ptr::<u8>().add(start) };
+		Ok(NonNull::slice_from_raw_parts(unsafe { NonNull::new_unchecked(ptr) }, layout.size()))
+	}
+
+	unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+		// Zero memory immediately
+		zero(ptr.as_ptr(), layout.size());
+
+		// Reclaim memory if this was the most recent allocation
+		let start = ptr.as_ptr().offset_from(self.alloc.inner().as_ptr::<u8>()) as usize;
+		if start + layout.size() == self.offset.get() {
+			self.offset.set(start);
+		}
+	}
+}
+
+impl Drop for Arena {
+	fn drop(&mut self) {
+		self.reset();
+
+		if likely(!self.alloc.inner().is_empty()) {
+			// Attempt to unlock memory
+			let _ = self.alloc.inner().unlock();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn boxed() {
+		let arena = Arena::new(4096).unwrap();
+
+		{
+			let a = std::boxed::Box::new_in(0x55555555u32, &arena);
+			let b = std::boxed::Box::new_in([0xffu8; 32], &arena);
+
+			assert_eq!(*a, 0x55555555);
+			assert_eq!(*b, [0xff; 32]);
+			assert!(arena.used() >= 36);
+		}
+
+		assert_eq!(arena.used(), 0);
+	}
+
+	#[test]
+	fn vec() {
+		let arena = Arena::new(65536).unwrap();
+		let mut test = std::vec::Vec::new_in(&arena);
+
+		for i in 0..1024usize {
+			test.push(i);
+		}
+
+		for (i, elem) in test.iter().enumerate() {
+			assert_eq!(*elem, i);
+		}
+	}
+
+	#[test]
+	fn exhausted() {
+		let arena = Arena::new(4096).unwrap();
+		let capacity = arena.capacity();
+
+		let _a = std::boxed::Box::<[u8], _>::new_zeroed_slice_in(capacity, &arena);
+		assert_eq!(arena.available(), 0);
+		assert!(std::boxed::Box::try_new_in(0u8, &arena).is_err());
+	}
+
+	#[test]
+	fn reset() {
+		let mut arena = Arena::new(4096).unwrap();
+		let ptr = {
+			let mut secret = std::mem::ManuallyDrop::new(std::boxed::Box::new_in([0x55u8; 64], &arena));
+			std::ptr::addr_of_mut!(**secret).cast::<u8>()
+		};
+
+		assert_eq!(arena.used(), 64);
+		arena.reset();
+		assert_eq!(arena.used(), 0);
+
+		for i in 0..64 {
+			assert_eq!(unsafe { ptr.add(i).read_volatile() }, 0);
+		}
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn guard() {
+		use bulletproof::Bulletproof;
+
+		let arena = Arena::new(4096).unwrap();
+		let bp = unsafe { Bulletproof::new() };
+		let ptr = arena.alloc.inner().as_ptr::<u8>();
+
+		// Preceding guard
+		for i in 1..=GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::guard_size() {
+			assert_eq!(unsafe { bp.load(ptr.sub(i)) }, Err(()));
+		}
+
+		// Trailing guard
+		for i in arena.capacity() .. arena.capacity() + GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::guard_size() {
+			assert_eq!(unsafe { bp.load(ptr.add(i)) }, Err(()));
+		}
+	}
+}
@@ -0,0 +1,191 @@
+//! Access auditing
+//!
+//! A [`Guard`](crate::guard::Guard) reports to an audit [`Hook`] whenever its contents become accessible on first
+//! acquisition and inaccessible again on last release. Guards without a hook of their own report to the
+//! [default hook](set_default_hook), if any.
+//!
+//! Upgrading or downgrading a reference in place is reported as a release of the previous kind of borrow followed
+//! by an acquisition of the new one, so that events of each kind remain paired.
+
+use std::collections::VecDeque;
+use std::panic::Location;
+use std::sync::{Mutex, PoisonError, RwLock};
+use std::time::SystemTime;
+
+/// Kind of borrow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+	Immutable,
+	Mutable,
+}
+
+/// Transition of the contents’ accessibility
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+	/// Contents became accessible
+	Acquire,
+
+	/// Contents became inaccessible
+	Release,
+}
+
+/// Access event
+#[derive(Clone, Copy, Debug)]
+pub struct Event {
+	pub kind: Kind,
+	pub phase: Phase,
+
+	/// Location of the borrow that made the contents accessible
+	pub location: &'static Location<'static>,
+
+	pub time: SystemTime,
+}
+
+/// Audit hook
+pub trait Hook: Send + Sync {
+	fn record(&self, event: &Event);
+}
+
+impl<F: Fn(&Event) + Send + Sync> Hook for F {
+	#[inline]
+	fn record(&self, event: &Event) {
+		self(event);
+	}
+}
+
+/// Bounded in‐memory ring of access events
+///
+/// Once full, the oldest events are discarded.
+#[derive(Debug)]
+pub struct Ring {
+	capacity: usize,
+	events: Mutex<VecDeque<Event>>,
+}
+
+static DEFAULT: RwLock<Option<&'static dyn Hook>> = RwLock::new(None);
+
+/// Set hook for guards without a hook of their own
+pub fn set_default_hook(hook: Option<&'static dyn Hook>) {
+	*DEFAULT.write().unwrap_or_else(PoisonError::into_inner) = hook;
+}
+
+#[must_use]
+pub fn default_hook() -> Option<&'static dyn Hook> {
+	*DEFAULT.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Ring {
+	#[must_use]
+	pub const fn new(capacity: usize) -> Self {
+		Self { capacity, events: Mutex::new(VecDeque::new()) }
+	}
+
+	#[must_use] #[inline]
+	pub const fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Recorded events, oldest first
+	#[must_use]
+	pub fn events(&self) -> Vec<Event> {
+		self.events.lock().unwrap_or_else(PoisonError::into_inner).iter().copied().collect()
+	}
+
+	pub fn clear(&self) {
+		self.events.lock().unwrap_or_else(PoisonError::into_inner).clear();
+	}
+}
+
+impl Hook for Ring {
+	fn record(&self, event: &Event) {
+		if self.capacity == 0 {
+			return;
+		}
+
+		let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+
+		if events.len() == self.capacity {
+			events.pop_front();
+		}
+
+		events.push_back(*event);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::boxed::Box;
+
+	#[test]
+	fn ring() {
+		static RING: Ring = Ring::new(4);
+
+		let mut test = Box::<u32>::new(0x55555555);
+		test.set_audit_hook(Some(&RING));
+
+		let line = line!() + 1;
+		let immutable = test.borrow();
+		let other = test.borrow();
+		drop(immutable);
+		drop(other);
+
+		let events = RING.events();
+		assert_eq!(events.len(), 2);
+		assert_eq!((events[0].kind, events[0].phase), (Kind::Immutable, Phase::Acquire));
+		assert_eq!((events[1].kind, events[1].phase), (Kind::Immutable, Phase::Release));
+		assert_eq!((events[0].location.file(), events[0].location.line()), (file!(), line));
+		assert_eq!(events[1].location, events[0].location);
+		assert!(events[0].time <= events[1].time);
+
+		for _ in 0..3 {
+			*test.borrow_mut() = 0xdeadbeef;
+		}
+
+		let events = RING.events();
+		assert_eq!(events.len(), RING.capacity());
+		assert!(events.iter().all(|event| event.kind == Kind::Mutable));
+		assert_eq!(events[3].phase, Phase::Release);
+	}
+
+	#[test]
+	fn transition() {
+		use crate::guard::{Ref, RefMut};
+
+		static RING: Ring = Ring::new(8);
+
+		let mut test = Box::<u32>::new(0x55555555);
+		test.set_audit_hook(Some(&RING));
+
+		drop(Ref::try_upgrade(test.borrow()).unwrap());
+		drop(RefMut::downgrade(test.borrow_mut()));
+
+		let events: Vec<_> = RING.events().into_iter().map(|event| (event.kind, event.phase)).collect();
+		assert_eq!(events, [
+			(Kind::Immutable, Phase::Acquire), (Kind::Immutable, Phase::Release),
+			(Kind::Mutable, Phase::Acquire), (Kind::Mutable, Phase::Release),
+			(Kind::Mutable, Phase::Acquire), (Kind::Mutable, Phase::Release),
+			(Kind::Immutable, Phase::Acquire), (Kind::Immutable, Phase::Release),
+		]);
+	}
+
+	#[test]
+	fn default() {
+		static RING: Ring = Ring::new(1024);
+
+		let test = Box::<u32>::new(0x55555555);
+
+		set_default_hook(Some(&RING));
+		let line = line!() + 1;
+		drop(test.borrow());
+		set_default_hook(None);
+
+		// Other tests may record concurrently
+		let events: Vec<_> = RING.events().into_iter()
+			.filter(|event| event.location.file() == file!() && event.location.line() == line).collect();
+
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].phase, Phase::Acquire);
+		assert_eq!(events[1].phase, Phase::Release);
+	}
+}
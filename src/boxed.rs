@@ -3,18 +3,39 @@
 use crate::auxiliary::zero;
 use crate::pages::{Pages, GuardedAlloc};
 use crate::alloc::Sensitive;
+use crate::error::Error;
 use crate::guard::Guard;
+use crate::redact::{Mask, Maskable, Masked};
 use crate::traits::{AsPages, Protectable};
 
+use std::clone::CloneToUninit;
+use std::fmt;
+use std::ops::CoerceUnsized;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
 pub(crate) type InnerBox<T> = std::boxed::Box<T, Sensitive>;
 
 /// Guarded [box](std::boxed::Box) type
 pub type Box<T> = Guard<InnerBox<T>>;
 
-impl<T> AsPages for InnerBox<T> {
+impl<T: ?Sized> AsPages for InnerBox<T> {
 	fn as_pages(&self) -> Option<Pages> {
-		if std::mem::size_of::<T>() > 0 {
-			Some(unsafe { GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::from_ptr(std::ptr::addr_of!(**self) as *mut T, std::mem::size_of::<T>()).into_pages() })
+		let ptr = &raw const **self;
+		let size = unsafe { std::mem::size_of_val_raw(ptr) };
+
+		if size > 0 {
+			Some(unsafe { GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::from_ptr(ptr.cast_mut().cast::<u8>(), size).into_pages() })
+		} else {
+			None
+		}
+	}
+
+	fn as_outer_pages(&self) -> Option<Pages<'_>> {
+		let ptr = &raw const **self;
+		let size = unsafe { std::mem::size_of_val_raw(ptr) };
+
+		if size > 0 {
+			Some(unsafe { GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::from_ptr(ptr.cast_mut().cast::<u8>(), size).into_outer_pages() })
 		} else {
 			None
 		}
@@ -22,21 +43,412 @@ impl<T> AsPages for InnerBox<T> {
 }
 
 impl<T> Box<T> {
+	pub(crate) fn try_new_without_clear_in(source: T, alloc: Sensitive) -> Result<Self, Error> {
+		let mut guard = Guard::from_inner(std::boxed::Box::try_new_in(source, alloc)?);
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
 	pub(crate) fn new_without_clear(source: T) -> Self {
-		let mut guard = Guard::from_inner(std::boxed::Box::new_in(source, Sensitive));
-		guard.mutate(|boxed| boxed.lock().unwrap());
-		guard
+		Self::try_new_without_clear_in(source, Sensitive).unwrap()
 	}
 
-	pub fn new(mut source: T) -> Self {
-		let ptr: *mut T = &mut source;
-		let guard = Self::new_without_clear(source);
+	/// Move value at `source` into guarded memory, clearing out `source`
+	///
+	/// # Safety
+	///
+	/// `source` must point to a valid value, which is moved out and must not be dropped in place.
+	#[inline(always)]
+	unsafe fn try_move_in(source: *mut T, alloc: Sensitive) -> Result<Self, Error> {
+		let guard = Self::try_new_without_clear_in(source.read(), alloc);
 
 		// Clear out source
-		unsafe { zero(ptr, 1); }
+		zero(source, 1);
 
 		guard
 	}
+
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_new_in(source: T, alloc: Sensitive) -> Result<Self, Error> {
+		let mut source = ManuallyDrop::new(source);
+		unsafe { Self::try_move_in((&raw mut source).cast(), alloc) }
+	}
+
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_new(source: T) -> Result<Self, Error> {
+		let mut source = ManuallyDrop::new(source);
+		unsafe { Self::try_move_in((&raw mut source).cast(), Sensitive) }
+	}
+
+	pub fn new_in(source: T, alloc: Sensitive) -> Self {
+		let mut source = ManuallyDrop::new(source);
+		unsafe { Self::try_move_in((&raw mut source).cast(), alloc) }.unwrap()
+	}
+
+	pub fn new(source: T) -> Self {
+		let mut source = ManuallyDrop::new(source);
+		unsafe { Self::try_move_in((&raw mut source).cast(), Sensitive) }.unwrap()
+	}
+}
+
+impl<T> Box<T> {
+	/// Allocate guarded memory for a value without initialising it
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_new_uninit_in(alloc: Sensitive) -> Result<Box<MaybeUninit<T>>, Error> {
+		let mut guard = Guard::from_inner(InnerBox::<T>::try_new_uninit_in(alloc)?);
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_new_uninit() -> Result<Box<MaybeUninit<T>>, Error> {
+		Self::try_new_uninit_in(Sensitive)
+	}
+
+	#[inline]
+	pub fn new_uninit() -> Box<MaybeUninit<T>> {
+		Self::try_new_uninit().unwrap()
+	}
+
+	/// Allocate guarded memory for a value filled with zero bytes
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_new_zeroed_in(alloc: Sensitive) -> Result<Box<MaybeUninit<T>>, Error> {
+		let mut guard = Guard::from_inner(InnerBox::<T>::try_new_zeroed_in(alloc)?);
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_new_zeroed() -> Result<Box<MaybeUninit<T>>, Error> {
+		Self::try_new_zeroed_in(Sensitive)
+	}
+
+	#[inline]
+	pub fn new_zeroed() -> Box<MaybeUninit<T>> {
+		Self::try_new_zeroed().unwrap()
+	}
+
+	/// Initialise value directly in guarded memory
+	///
+	/// The memory is protected once `init` returns.
+	///
+	/// # Safety
+	///
+	/// `init` must initialise the value.
+	#[allow(clippy::missing_errors_doc)]
+	pub unsafe fn try_new_with_in<F>(init: F, alloc: Sensitive) -> Result<Self, Error>
+		where F: FnOnce(&mut MaybeUninit<T>) {
+		let mut boxed = InnerBox::<T>::try_new_uninit_in(alloc)?;
+		init(&mut boxed);
+
+		let mut guard = Guard::from_inner(boxed.assume_init());
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	/// Initialise value directly in guarded memory
+	///
+	/// # Safety
+	///
+	/// `init` must initialise the value.
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub unsafe fn try_new_with<F>(init: F) -> Result<Self, Error>
+		where F: FnOnce(&mut MaybeUninit<T>) {
+		Self::try_new_with_in(init, Sensitive)
+	}
+
+	/// Initialise value directly in guarded memory
+	///
+	/// # Safety
+	///
+	/// `init` must initialise the value.
+	#[inline]
+	pub unsafe fn new_with<F>(init: F) -> Self
+		where F: FnOnce(&mut MaybeUninit<T>) {
+		Self::try_new_with(init).unwrap()
+	}
+}
+
+impl<T: ?Sized> Box<T> {
+	/// Convert into box of unsized type, such as a trait object
+	///
+	/// The value remains in place and is dropped through the vtable when the box is dropped.
+	///
+	/// # Panics
+	///
+	/// Panics if the contents have been wiped.
+	pub fn unsize<U: ?Sized>(self) -> Box<U>
+		where InnerBox<T>: CoerceUnsized<InnerBox<U>> {
+		self.convert(|boxed| -> InnerBox<U> { boxed })
+	}
+}
+
+impl<T> Box<MaybeUninit<T>> {
+	/// Convert into initialised box
+	///
+	/// # Safety
+	///
+	/// The value must have been initialised.
+	///
+	/// # Panics
+	///
+	/// Panics if the contents have been wiped.
+	#[inline]
+	pub unsafe fn assume_init(self) -> Box<T> {
+		self.convert(|boxed| boxed.assume_init())
+	}
+}
+
+impl<T> Box<T> {
+	/// Move value out of guarded memory via `copy`, then wipe and free the guarded allocation
+	#[track_caller]
+	fn declassify_with<R, F>(self, copy: F) -> Result<R, Error>
+		where F: FnOnce(*const T) -> R {
+		let (ptr, alloc) = InnerBox::into_raw_with_allocator(self.into_unguarded()?);
+		let result = copy(ptr);
+
+		// Value has been moved out, so only zero and free its memory
+		drop(unsafe { InnerBox::<MaybeUninit<T>>::from_raw_in(ptr.cast(), alloc) });
+		Ok(result)
+	}
+
+	/// Move value out of guarded memory, wiping and freeing the guarded allocation
+	///
+	/// The value is no longer protected once moved out.
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn into_inner(self) -> Result<T, Error> {
+		self.declassify_with(|ptr| unsafe { ptr.read() })
+	}
+
+	/// Move value into a [standard box](std::boxed::Box), wiping and freeing the guarded allocation
+	///
+	/// The value is copied directly into the new allocation, which is not protected.
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn declassify(self) -> Result<std::boxed::Box<T>, Error> {
+		let mut dest = std::boxed::Box::<T>::new_uninit();
+		self.declassify_with(|ptr| unsafe { dest.as_mut_ptr().copy_from_nonoverlapping(ptr, 1) })?;
+		Ok(unsafe { dest.assume_init() })
+	}
+
+	/// Move value into `dest`, wiping and freeing the guarded allocation
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn declassify_into(self, dest: &mut MaybeUninit<T>) -> Result<&mut T, Error> {
+		self.declassify_with(|ptr| unsafe { dest.as_mut_ptr().copy_from_nonoverlapping(ptr, 1) })?;
+		Ok(unsafe { dest.assume_init_mut() })
+	}
+
+	/// Replace value with `value`, wiping the previous value
+	#[allow(clippy::missing_errors_doc)]
+	pub fn replace(&mut self, value: T) -> Result<(), Error> {
+		// Fail before the source is consumed
+		self.check_idle()?;
+
+		let alloc = *InnerBox::allocator(unsafe { self.inner() });
+		self.replace_with(Self::try_new_in(value, alloc)?)
+	}
+}
+
+impl<T: Default> Box<T> {
+	/// Take value, leaving the default value in its place
+	///
+	/// The value is not copied; its guarded allocation is moved to the returned guard.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn take(&mut self) -> Result<Self, Error> {
+		self.check_idle()?;
+
+		let alloc = *InnerBox::allocator(unsafe { self.inner() });
+		let mut taken = Self::try_new_without_clear_in(T::default(), alloc)?;
+		self.swap(&mut taken)?;
+		Ok(taken)
+	}
+}
+
+impl<T: Copy> Box<[T]> {
+	/// Copy elements of `source` into guarded memory, clearing out `source`
+	///
+	/// As `source` is zeroed in place, its elements must be [`Copy`], so that no destructor runs on zeroed values.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_slice_in(source: &mut [T], alloc: Sensitive) -> Result<Self, Error> {
+		let len = source.len();
+		let mut boxed = InnerBox::<[T]>::try_new_uninit_slice_in(len, alloc)?;
+
+		unsafe {
+			boxed.as_mut_ptr().cast::<T>().copy_from_nonoverlapping(source.as_ptr(), len);
+			zero(source.as_mut_ptr(), len);
+		}
+
+		let mut guard = Guard::from_inner(unsafe { boxed.assume_init() });
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_from_slice(source: &mut [T]) -> Result<Self, Error> {
+		Self::try_from_slice_in(source, Sensitive)
+	}
+}
+
+impl<T> Box<[T]> {
+	/// Allocate guarded memory for `len` elements filled with zero bytes
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_new_zeroed_slice_in(len: usize, alloc: Sensitive) -> Result<Box<[MaybeUninit<T>]>, Error> {
+		let mut guard = Guard::from_inner(InnerBox::<[T]>::try_new_zeroed_slice_in(len, alloc)?);
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_new_zeroed_slice(len: usize) -> Result<Box<[MaybeUninit<T>]>, Error> {
+		Self::try_new_zeroed_slice_in(len, Sensitive)
+	}
+
+	#[inline]
+	pub fn new_zeroed_slice(len: usize) -> Box<[MaybeUninit<T>]> {
+		Self::try_new_zeroed_slice(len).unwrap()
+	}
+
+	/// Initialise `len` elements directly in guarded memory with the results of calling `f` with their indices
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_fn_in<F>(len: usize, mut f: F, alloc: Sensitive) -> Result<Self, Error>
+		where F: FnMut(usize) -> T {
+		let mut boxed = InnerBox::<[T]>::try_new_uninit_slice_in(len, alloc)?;
+
+		for (index, elem) in boxed.iter_mut().enumerate() {
+			elem.write(f(index));
+		}
+
+		let mut guard = Guard::from_inner(unsafe { boxed.assume_init() });
+		guard.mutate(|boxed| boxed.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_from_fn<F>(len: usize, f: F) -> Result<Self, Error>
+		where F: FnMut(usize) -> T {
+		Self::try_from_fn_in(len, f, Sensitive)
+	}
+
+	#[inline]
+	pub fn from_fn<F>(len: usize, f: F) -> Self
+		where F: FnMut(usize) -> T {
+		Self::try_from_fn(len, f).unwrap()
+	}
+
+	/// Number of elements, determined without access to the contents
+	#[must_use] #[inline]
+	pub fn len(&self) -> usize {
+		if self.is_wiped() { 0 } else { (&raw const **unsafe { self.inner() }).len() }
+	}
+
+	#[must_use] #[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl<T> Box<[MaybeUninit<T>]> {
+	/// Convert into initialised box
+	///
+	/// # Safety
+	///
+	/// All elements must have been initialised.
+	///
+	/// # Panics
+	///
+	/// Panics if the contents have been wiped.
+	#[inline]
+	pub unsafe fn assume_init(self) -> Box<[T]> {
+		self.convert(|boxed| boxed.assume_init())
+	}
+}
+
+impl<T: Copy> From<&mut [T]> for Box<[T]> {
+	#[inline]
+	fn from(source: &mut [T]) -> Self {
+		Self::try_from_slice(source).unwrap()
+	}
+}
+
+impl Box<str> {
+	/// Move `source` into guarded memory, clearing it out
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_str_in(source: &mut str, alloc: Sensitive) -> Result<Self, Error> {
+		let guard = Box::<[u8]>::try_from_slice_in(unsafe { source.as_bytes_mut() }, alloc)?;
+		Ok(guard.convert(|boxed| {
+			let (ptr, alloc) = InnerBox::into_raw_with_allocator(boxed);
+			unsafe { InnerBox::from_raw_in(ptr as *mut str, alloc) }
+		}))
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_from_str(source: &mut str) -> Result<Self, Error> {
+		Self::try_from_str_in(source, Sensitive)
+	}
+
+	/// Length in bytes, determined without access to the contents
+	#[must_use] #[inline]
+	pub fn len(&self) -> usize {
+		if self.is_wiped() { 0 } else { (&raw const **unsafe { self.inner() } as *const [u8]).len() }
+	}
+
+	#[must_use] #[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl From<&mut str> for Box<str> {
+	#[inline]
+	fn from(source: &mut str) -> Self {
+		Self::try_from_str(source).unwrap()
+	}
+}
+
+impl<T: Clone> Box<T> {
+	/// Clone into a new guarded allocation
+	///
+	/// The value is cloned directly from the source allocation, which is immutably borrowed for the duration of the
+	/// copy, into the new allocation, which is protected once the copy is complete.
+	///
+	/// The borrow for cloning is taken from the [access budget](Guard::set_budget) first. The remaining budget is then
+	/// split between the original and the clone, which receives the smaller half, so that cloning does not add up to
+	/// more accesses than the original had left. Fails with [`Error::Exhausted`] if fewer than two accesses remain.
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn try_clone(&self) -> Result<Self, Error> {
+		let source = self.try_borrow()?;
+		let mut boxed = InnerBox::<T>::try_new_uninit_in(*InnerBox::allocator(source.inner()))?;
+
+		unsafe { (**source.inner()).clone_to_uninit(boxed.as_mut_ptr().cast()); }
+
+		let mut guard = Guard::from_inner(unsafe { boxed.assume_init() });
+		guard.mutate(|boxed| boxed.lock())?;
+		guard.set_budget(self.split_budget()?)?;
+		Ok(guard)
+	}
+}
+
+impl<T: Clone> Clone for Box<T> {
+	#[track_caller]
+	fn clone(&self) -> Self {
+		self.try_clone().unwrap()
+	}
+}
+
+impl<T: Maskable + ?Sized> Box<T> {
+	/// Format contents, revealing only what `mask` permits
+	#[inline]
+	pub fn masked(&self, mask: Mask) -> Masked<'_, Self> {
+		Masked { guard: self, mask }
+	}
+}
+
+impl<T: Maskable + ?Sized> fmt::Display for Masked<'_, Box<T>> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.guard.try_borrow() {
+			Ok(boxed) => (**boxed.inner()).write_masked(fmt, &self.mask),
+			Err(_) => fmt.write_str("<redacted>"),
+		}
+	}
 }
 
 impl<T: Default> Default for Box<T> {
@@ -48,6 +460,7 @@ impl<T: Default> Default for Box<T> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::error::BorrowError;
 
 	#[cfg(target_os = "linux")]
 	#[test]
@@ -78,4 +491,340 @@ mod tests {
 
 		assert_eq!(unsafe { bp.load(ptr) }, Err(()));
 	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn transition() {
+		use crate::guard::{Ref, RefMut};
+		use bulletproof::Bulletproof;
+
+		let mut test = Box::<u32>::new(0x55555555);
+		let bp = unsafe { Bulletproof::new() };
+
+		let ptr = unsafe { &mut **test.inner_mut() } as *mut u32;
+
+		{
+			let immutable = RefMut::downgrade(test.borrow_mut());
+			assert_eq!(*immutable, 0x55555555);
+			assert_eq!(unsafe { bp.store(ptr, &0x55) }, Err(()));
+
+			let mut mutable = Ref::try_upgrade(immutable).unwrap();
+			*mutable = 0xdeadbeef;
+			assert_eq!(unsafe { bp.load(ptr) }, Ok(0xdeadbeef));
+		}
+
+		assert_eq!(unsafe { bp.load(ptr) }, Err(()));
+		assert_eq!(*test.borrow(), 0xdeadbeef);
+	}
+
+	#[test]
+	fn read_once() {
+		let mut test = Box::<u32>::new(0x55555555);
+		test.set_budget(Some(1)).unwrap();
+
+		assert_eq!(*test.borrow(), 0x55555555);
+		assert!(test.is_wiped());
+		assert!(matches!(test.try_borrow(), Err(BorrowError::Wiped)));
+		assert!(matches!(test.try_borrow_mut(), Err(BorrowError::Wiped)));
+	}
+
+	#[test]
+	fn clone() {
+		let mut test = Box::<[u8; 64]>::new([0x55; 64]);
+		test.set_budget(Some(6)).unwrap();
+
+		// Remaining budget is split with the clone
+		let mut clone = test.clone();
+		assert_eq!(test.budget(), Some(3));
+		assert_eq!(clone.budget(), Some(2));
+
+		clone.with_mut(|clone| clone[0] = 0xff);
+		assert_eq!(test.with(|test| test[0]), 0x55);
+		assert_eq!(clone.with(|clone| [clone[0], clone[1]]), [0xff, 0x55]);
+		assert!(clone.is_wiped());
+
+		// Cloning without budget left to split fails
+		assert!(matches!(test.try_clone(), Err(Error::Exhausted)));
+		assert!(matches!(test.try_clone(), Err(Error::Exhausted)));
+		assert!(test.is_wiped());
+
+		// Borrow state of the source is respected
+		assert!(matches!(test.try_clone(), Err(Error::Wiped)));
+	}
+
+	#[test]
+	fn map() {
+		use crate::guard::{Ref, RefMut, MappedRef, MappedRefMut};
+
+		#[derive(Debug)]
+		struct KeyPair {
+			public: [u8; 32],
+			private: [u8; 32],
+		}
+
+		fn private(key: &Box<KeyPair>) -> MappedRef<'_, [u8; 32]> {
+			Ref::map(key.borrow(), |key| &key.private)
+		}
+
+		let mut test = Box::new(KeyPair { public: [0x55; 32], private: [0xaa; 32] });
+
+		{
+			let mutable = test.borrow_mut();
+			let (mut public, mut private) = RefMut::map_split(mutable, |key| (&mut key.public, &mut key.private));
+			public[0] = 0x00;
+			private[0] = 0xff;
+			drop(public);
+			private[1] = 0xff;
+		}
+
+		{
+			let mut private = RefMut::map(test.borrow_mut(), |key| &mut key.private);
+			private[2] = 0xff;
+
+			let mut first = MappedRefMut::map(private, |private| &mut private[0]);
+			*first = 0x11;
+		}
+
+		{
+			let (public, private) = Ref::map_split(test.borrow(), |key| (&key.public, &key.private));
+			assert_eq!(public[..2], [0x00, 0x55]);
+			drop(public);
+			assert_eq!(private[..4], [0x11, 0xff, 0xff, 0xaa]);
+		}
+
+		assert_eq!(*private(&test), *Ref::map(test.borrow(), |key| &key.private));
+		assert!(Ref::filter_map(test.borrow(), |key| key.public.get(32)).is_err());
+		assert_eq!(*Ref::filter_map(test.borrow(), |key| key.public.get(31)).unwrap(), 0x55);
+
+		assert!(format!("{:?}", test).starts_with("Guard(0,"));
+	}
+
+	#[test]
+	fn masked() {
+		let test = Box::<[u8; 4]>::new([0xde, 0xad, 0xbe, 0xef]);
+
+		assert_eq!(test.masked(Mask::new().suffix(2)).to_string(), "****beef");
+		assert_eq!(format!("{:?}", test.masked(Mask::new().prefix(1))), "\"de******\"");
+		assert!(!format!("{test:?}").contains("de"));
+
+		let _immutable = test.borrow();
+		assert_eq!(test.masked(Mask::new().suffix(2)).to_string(), "****beef");
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn freeze() {
+		use crate::traits::AsPages;
+		use crate::pages::Protection;
+		use bulletproof::Bulletproof;
+
+		let mut test = Box::<u32>::new(0x55555555);
+		let bp = unsafe { Bulletproof::new() };
+
+		test.freeze().unwrap();
+		assert!(test.is_frozen());
+		assert!(matches!(test.try_borrow_mut(), Err(BorrowError::Frozen)));
+		assert!(matches!(test.into_inner(), Err(Error::Frozen)));
+
+		let mut test = Box::<u32>::new(0x55555555);
+		let ptr = unsafe { &mut **test.inner_mut() } as *mut u32;
+		test.freeze().unwrap();
+
+		{
+			let immutable = test.borrow();
+			assert_eq!(*immutable, 0x55555555);
+			assert_eq!(unsafe { bp.store(ptr, &0xdeadbeef) }, Err(()));
+		}
+
+		if test.is_sealed() {
+			// Protection can no longer be changed, nor the contents wiped
+			assert_eq!(unsafe { bp.load(ptr) }, Ok(0x55555555));
+			assert!(unsafe { test.inner() }.as_pages().unwrap().protect(Protection::ReadWrite).is_err());
+			assert!(matches!(test.set_budget(Some(0)), Err(Error::Frozen)));
+		} else {
+			assert_eq!(unsafe { bp.load(ptr) }, Err(()));
+			test.set_budget(Some(0)).unwrap();
+			assert!(test.is_wiped());
+		}
+	}
+
+	#[test]
+	fn declassify() {
+		let test = Box::<[u8; 32]>::new([0x55; 32]);
+		assert_eq!(test.into_inner().unwrap(), [0x55; 32]);
+
+		let test = Box::<[u8; 32]>::new([0xaa; 32]);
+		assert_eq!(*test.declassify().unwrap(), [0xaa; 32]);
+
+		let mut dest = MaybeUninit::uninit();
+		assert_eq!(*Box::<u32>::new(0xdeadbeef).declassify_into(&mut dest).unwrap(), 0xdeadbeef);
+
+		let mut test = Box::<u32>::new(0x55555555);
+		test.set_budget(Some(0)).unwrap();
+		assert!(matches!(test.into_inner(), Err(Error::Wiped)));
+	}
+
+	#[test]
+	fn replace() {
+		let mut test = Box::<u32>::new(0x55555555);
+		let mut other = Box::<u32>::new(0xdeadbeef);
+
+		test.swap(&mut other).unwrap();
+		assert_eq!((*test.borrow(), *other.borrow()), (0xdeadbeef, 0x55555555));
+
+		test.replace(0x12345678).unwrap();
+		assert_eq!(*test.borrow(), 0x12345678);
+
+		let taken = test.take().unwrap();
+		assert_eq!((*test.borrow(), *taken.borrow()), (0, 0x12345678));
+
+		other.set_budget(Some(0)).unwrap();
+		assert!(matches!(test.swap(&mut other), Err(Error::Wiped)));
+
+		let mut poisoned = Box::<u32>::new(0x55555555);
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let mut value = poisoned.borrow_mut();
+			*value = 0xdeadbeef;
+			panic!();
+		})).is_err());
+
+		// Torn contents must not escape into an unpoisoned guard
+		assert!(matches!(poisoned.take(), Err(Error::Poisoned)));
+		assert!(matches!(poisoned.replace(0), Err(Error::Poisoned)));
+		assert!(matches!(test.swap(&mut poisoned), Err(Error::Poisoned)));
+		assert!(matches!(poisoned.swap(&mut test), Err(Error::Poisoned)));
+		assert!(poisoned.is_poisoned() && !test.is_poisoned());
+
+		poisoned.clear_poison();
+		assert_eq!(*poisoned.take().unwrap().borrow(), 0xdeadbeef);
+	}
+
+	#[test]
+	fn uninit() {
+		let mut test = Box::<u32>::new_uninit();
+		test.set_budget(Some(2)).unwrap();
+		test.borrow_mut().write(0xdeadbeef);
+
+		let test = unsafe { test.assume_init() };
+		assert_eq!(test.budget(), Some(1));
+		assert_eq!(*test.borrow(), 0xdeadbeef);
+		assert!(test.is_wiped());
+
+		let test = unsafe { Box::<u32>::new_with(|value| { value.write(0x55555555); }) };
+		assert_eq!(*test.borrow(), 0x55555555);
+	}
+
+	#[test]
+	fn large() {
+		use std::thread;
+
+		// Constructed without passing through the stack
+		thread::Builder::new().stack_size(64 * 1024).spawn(|| {
+			let test = unsafe { Box::<[u8; 1 << 20]>::new_zeroed().assume_init() };
+			assert!(test.with(|test| test.iter().all(|&byte| byte == 0)));
+
+			let test = unsafe { Box::<[u8; 1 << 20]>::new_with(|value| {
+				value.as_mut_ptr().cast::<u8>().write_bytes(0x55, 1 << 20);
+			}) };
+
+			assert!(test.with(|test| test.iter().all(|&byte| byte == 0x55)));
+		}).unwrap().join().unwrap();
+	}
+
+	#[test]
+	fn slice() {
+		let mut source = [0x55u8; 64];
+		let test = Box::<[u8]>::from(source.as_mut_slice());
+		assert_eq!(source, [0; 64]);
+		assert_eq!(test.len(), 64);
+		assert_eq!(test.borrow()[..], [0x55; 64]);
+
+		let test = Box::<[u16]>::from_fn(4096, |index| index as u16);
+		assert!(test.with(|test| test.iter().enumerate().all(|(index, &elem)| elem == index as u16)));
+
+		let test = unsafe { Box::<[u32]>::new_zeroed_slice(16).assume_init() };
+		assert_eq!(test.borrow()[..], [0; 16]);
+
+		let test = Box::<[u8]>::from_fn(0, |_| 0);
+		assert!(test.is_empty());
+		assert_eq!(test.borrow().len(), 0);
+	}
+
+	#[test]
+	fn str() {
+		let mut source = std::string::String::from("Thunfisch");
+		let test = Box::<str>::from(source.as_mut_str());
+
+		assert_eq!(source.as_bytes(), [0; 9]);
+		assert_eq!(test.len(), 9);
+		assert_eq!(&*test.borrow(), "Thunfisch");
+
+		#[cfg(feature = "string")]
+		{
+			assert_eq!(test.masked(Mask::new().prefix(1).suffix(1)).to_string(), "T*******h");
+
+			let test = Box::<str>::from(std::string::String::from("ne\u{301}e\u{301}").as_mut_str());
+			assert_eq!(test.masked(Mask::new().prefix(1)).to_string(), "n**");
+		}
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn slice_protection() {
+		use bulletproof::Bulletproof;
+
+		let mut test = Box::<[u32]>::from_fn(2048, |_| 0x55555555);
+		let bp = unsafe { Bulletproof::new() };
+		let ptr = unsafe { test.inner_mut() }.as_mut_ptr().wrapping_add(2047);
+
+		assert_eq!(unsafe { bp.load(ptr) }, Err(()));
+		assert_eq!(test.borrow()[2047], 0x55555555);
+		assert_eq!(unsafe { bp.load(ptr) }, Err(()));
+	}
+
+	#[test]
+	fn unsize() {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+		trait Signer {
+			fn sign(&self, message: &[u8]) -> u8;
+		}
+
+		struct Key([u8; 32]);
+
+		impl Signer for Key {
+			fn sign(&self, message: &[u8]) -> u8 {
+				message.iter().zip(self.0.iter()).fold(0, |sig, (a, b)| sig ^ a ^ b)
+			}
+		}
+
+		impl Drop for Key {
+			fn drop(&mut self) {
+				// Requires access to the contents
+				self.0.fill(0);
+				DROPPED.fetch_add(1, Ordering::Relaxed);
+			}
+		}
+
+		let mut test: Box<dyn Signer> = Box::new(Key([0x55; 32])).unsize();
+		test.set_budget(Some(2)).unwrap();
+		assert!(unsafe { test.inner() }.as_pages().is_some());
+
+		assert_eq!(test.borrow().sign(&[0xff; 2]), 0);
+		assert_eq!(test.with(|signer| signer.sign(&[0xff])), 0xaa);
+		assert!(test.is_wiped());
+		assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+
+		drop(Box::new(Key([0xaa; 32])).unsize::<dyn Signer>());
+		assert_eq!(DROPPED.load(Ordering::Relaxed), 2);
+	}
+
+	#[test]
+	fn try_new() {
+		let test = Box::<[u8; 32]>::try_new([0x55; 32]).unwrap();
+		assert_eq!(*test.borrow(), [0x55; 32]);
+	}
 }
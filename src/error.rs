@@ -0,0 +1,196 @@
+//! Error types
+
+use std::alloc::AllocError;
+use std::collections::TryReserveError;
+use std::fmt;
+
+/// Error type for fallible operations
+#[derive(Debug)]
+pub enum Error {
+	/// Memory could not be allocated
+	Alloc,
+
+	/// Memory protection could not be changed
+	Protect(std::io::Error),
+
+	/// Reference counter would overflow
+	Overflow,
+
+	/// Guard is still borrowed
+	Borrowed,
+
+	/// Contents have been wiped
+	Wiped,
+
+	/// Access budget has been exhausted
+	Exhausted,
+
+	/// Deadline has passed
+	Expired,
+
+	/// Guard has been frozen
+	Frozen,
+
+	/// Guard has been poisoned
+	Poisoned,
+
+	/// Input is not validly encoded
+	Encoding,
+
+	/// Random bytes could not be generated
+	Random,
+}
+
+/// Error type for fallible borrows
+///
+/// If the guard has been [poisoned](Self::Poisoned), the reference is acquired regardless and may be recovered.
+pub enum BorrowError<R> {
+	/// Memory protection could not be changed
+	Protect(std::io::Error),
+
+	/// Reference counter would overflow
+	Overflow,
+
+	/// Guard is still borrowed
+	Borrowed,
+
+	/// Contents have been wiped
+	Wiped,
+
+	/// Access budget has been exhausted
+	Exhausted,
+
+	/// Deadline has passed
+	Expired,
+
+	/// Guard has been frozen
+	Frozen,
+
+	/// Guard has been poisoned
+	Poisoned(R),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Alloc => write!(fmt, "memory allocation failed"),
+			Self::Protect(err) => write!(fmt, "memory protection failed: {}", err),
+			Self::Overflow => write!(fmt, "reference counter overflow"),
+			Self::Borrowed => write!(fmt, "already borrowed"),
+			Self::Wiped => write!(fmt, "contents wiped"),
+			Self::Exhausted => write!(fmt, "access budget exhausted"),
+			Self::Expired => write!(fmt, "deadline passed"),
+			Self::Frozen => write!(fmt, "guard frozen"),
+			Self::Poisoned => write!(fmt, "guard poisoned"),
+			Self::Encoding => write!(fmt, "invalid encoding"),
+			Self::Random => write!(fmt, "random number generation failed"),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Protect(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl<R> BorrowError<R> {
+	pub(crate) fn map<U, F>(self, map: F) -> BorrowError<U>
+		where F: FnOnce(R) -> U {
+		match self {
+			Self::Protect(err) => BorrowError::Protect(err),
+			Self::Overflow => BorrowError::Overflow,
+			Self::Borrowed => BorrowError::Borrowed,
+			Self::Wiped => BorrowError::Wiped,
+			Self::Exhausted => BorrowError::Exhausted,
+			Self::Expired => BorrowError::Expired,
+			Self::Frozen => BorrowError::Frozen,
+			Self::Poisoned(inner) => BorrowError::Poisoned(map(inner)),
+		}
+	}
+}
+
+impl<R> fmt::Debug for BorrowError<R> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Protect(err) => fmt.debug_tuple("Protect").field(err).finish(),
+			Self::Overflow => write!(fmt, "Overflow"),
+			Self::Borrowed => write!(fmt, "Borrowed"),
+			Self::Wiped => write!(fmt, "Wiped"),
+			Self::Exhausted => write!(fmt, "Exhausted"),
+			Self::Expired => write!(fmt, "Expired"),
+			Self::Frozen => write!(fmt, "Frozen"),
+			Self::Poisoned(_) => write!(fmt, "Poisoned(..)"),
+		}
+	}
+}
+
+impl<R> fmt::Display for BorrowError<R> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Protect(err) => write!(fmt, "memory protection failed: {}", err),
+			Self::Overflow => write!(fmt, "reference counter overflow"),
+			Self::Borrowed => write!(fmt, "already borrowed"),
+			Self::Wiped => write!(fmt, "contents wiped"),
+			Self::Exhausted => write!(fmt, "access budget exhausted"),
+			Self::Expired => write!(fmt, "deadline passed"),
+			Self::Frozen => write!(fmt, "guard frozen"),
+			Self::Poisoned(_) => write!(fmt, "guard poisoned"),
+		}
+	}
+}
+
+impl<R> std::error::Error for BorrowError<R> {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Protect(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl<R> From<std::io::Error> for BorrowError<R> {
+	#[inline]
+	fn from(err: std::io::Error) -> Self {
+		Self::Protect(err)
+	}
+}
+
+impl<R> From<BorrowError<R>> for Error {
+	fn from(err: BorrowError<R>) -> Self {
+		match err {
+			BorrowError::Protect(err) => Self::Protect(err),
+			BorrowError::Overflow => Self::Overflow,
+			BorrowError::Borrowed => Self::Borrowed,
+			BorrowError::Wiped => Self::Wiped,
+			BorrowError::Exhausted => Self::Exhausted,
+			BorrowError::Expired => Self::Expired,
+			BorrowError::Frozen => Self::Frozen,
+			BorrowError::Poisoned(_) => Self::Poisoned,
+		}
+	}
+}
+
+impl From<AllocError> for Error {
+	#[inline]
+	fn from(_: AllocError) -> Self {
+		Self::Alloc
+	}
+}
+
+impl From<TryReserveError> for Error {
+	#[inline]
+	fn from(_: TryReserveError) -> Self {
+		Self::Alloc
+	}
+}
+
+impl From<std::io::Error> for Error {
+	#[inline]
+	fn from(err: std::io::Error) -> Self {
+		Self::Protect(err)
+	}
+}
@@ -0,0 +1,354 @@
+//! Expiring guards
+//!
+//! An [`Expiring`] guard is wiped once its deadline has passed. The deadline is checked on each borrow. A
+//! [`Reaper`] additionally wipes expired guards that are not borrowed at all.
+
+use crate::error::BorrowError;
+use crate::guard::{Guard, Ref, RefMut};
+use crate::traits::Protectable;
+
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source of time for deadlines
+pub trait Clock: Send + Sync {
+	fn now(&self) -> Instant;
+}
+
+/// System monotonic clock
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+/// Manually advanced clock for deterministic tests
+#[derive(Debug)]
+pub struct ManualClock {
+	start: Instant,
+	elapsed: AtomicU64,
+}
+
+/// Guard wiped after a deadline
+///
+/// Once the deadline has passed, borrows fail with [`BorrowError::Expired`] and the contents are wiped as soon as
+/// no references remain. References acquired before the deadline remain valid. The contents of a
+/// [sealed](Guard::is_sealed) guard cannot be wiped, but borrows fail all the same.
+#[must_use]
+#[derive(Debug)]
+pub struct Expiring<T: Protectable, C: Clock = SystemClock> {
+	guard: Guard<T>,
+	deadline: Instant,
+	clock: C,
+}
+
+/// Wiper of expired guards
+///
+/// Guards are [watched](Self::watch) until they have been wiped or dropped, or have expired while sealed. Expired guards are wiped on each
+/// [`reap`](Self::reap), which a background thread may perform [periodically](Self::spawn).
+#[derive(Default)]
+pub struct Reaper {
+	watched: Mutex<Vec<Weak<dyn Expire>>>,
+}
+
+/// Type‐erased expiry for watched guards
+trait Expire: Send + Sync {
+	/// Wipe if expired, returning whether the guard need no longer be watched
+	fn expire(&self) -> bool;
+}
+
+impl Clock for SystemClock {
+	#[inline]
+	fn now(&self) -> Instant {
+		Instant::now()
+	}
+}
+
+impl ManualClock {
+	#[must_use]
+	pub fn new() -> Self {
+		Self { start: Instant::now(), elapsed: AtomicU64::new(0) }
+	}
+
+	/// Advance clock by `duration`
+	///
+	/// # Panics
+	///
+	/// Panics if the total elapsed time exceeds [`u64::MAX`] nanoseconds.
+	pub fn advance(&self, duration: Duration) {
+		self.elapsed.fetch_add(u64::try_from(duration.as_nanos()).unwrap(), Ordering::AcqRel);
+	}
+}
+
+impl Default for ManualClock {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Clock for ManualClock {
+	#[inline]
+	fn now(&self) -> Instant {
+		self.start + Duration::from_nanos(self.elapsed.load(Ordering::Acquire))
+	}
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+	#[inline]
+	fn now(&self) -> Instant {
+		(**self).now()
+	}
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+	#[inline]
+	fn now(&self) -> Instant {
+		(**self).now()
+	}
+}
+
+impl<T: Protectable> Expiring<T> {
+	/// Wrap `guard`, expiring after `ttl`
+	#[inline]
+	pub fn new(guard: Guard<T>, ttl: Duration) -> Self {
+		Self::with_clock(guard, ttl, SystemClock)
+	}
+}
+
+impl<T: Protectable, C: Clock> Expiring<T, C> {
+	/// Wrap `guard`, expiring after `ttl` as measured by `clock`
+	pub fn with_clock(guard: Guard<T>, ttl: Duration, clock: C) -> Self {
+		let deadline = clock.now() + ttl;
+		Self { guard, deadline, clock }
+	}
+
+	#[must_use] #[inline]
+	pub fn deadline(&self) -> Instant {
+		self.deadline
+	}
+
+	#[must_use] #[inline]
+	pub fn is_expired(&self) -> bool {
+		self.clock.now() >= self.deadline
+	}
+
+	#[must_use] #[inline]
+	pub fn is_wiped(&self) -> bool {
+		self.guard.is_wiped()
+	}
+
+	/// Wipe if expired, returning whether the deadline has passed
+	fn check(&self) -> bool {
+		let expired = self.is_expired();
+
+		if expired {
+			self.guard.exhaust();
+		}
+
+		expired
+	}
+
+	/// Immutably borrow guarded value, returning an error on failure or expiry
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow(&self) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		if self.check() {
+			return Err(BorrowError::Expired);
+		}
+
+		self.guard.try_borrow()
+	}
+
+	/// Mutably borrow guarded value, returning an error on failure or expiry
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow_mut(&mut self) -> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		if self.check() {
+			return Err(BorrowError::Expired);
+		}
+
+		self.guard.try_borrow_mut()
+	}
+
+	/// Immutably borrow guarded value
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed or has expired.
+	#[inline] #[track_caller]
+	pub fn borrow(&self) -> Ref<'_, T> {
+		self.try_borrow().unwrap()
+	}
+
+	/// Mutably borrow guarded value
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed or has expired.
+	#[inline] #[track_caller]
+	pub fn borrow_mut(&mut self) -> RefMut<'_, T> {
+		self.try_borrow_mut().unwrap()
+	}
+}
+
+impl<T: Protectable + Send + Sync, C: Clock> Expire for Expiring<T, C> {
+	fn expire(&self) -> bool {
+		// Sealed guards are never wiped
+		self.check() && (self.guard.is_wiped() || self.guard.is_sealed())
+	}
+}
+
+impl Reaper {
+	#[must_use]
+	pub const fn new() -> Self {
+		Self { watched: Mutex::new(Vec::new()) }
+	}
+
+	/// Create reaper with background thread reaping every `interval`
+	///
+	/// The thread terminates once the reaper is dropped.
+	#[must_use]
+	pub fn spawn(interval: Duration) -> Arc<Self> {
+		let reaper = Arc::new(Self::new());
+		let weak = Arc::downgrade(&reaper);
+
+		thread::spawn(move || loop {
+			thread::sleep(interval);
+
+			match weak.upgrade() {
+				Some(reaper) => reaper.reap(),
+				None => break,
+			}
+		});
+
+		reaper
+	}
+
+	/// Watch `guard` for expiry
+	pub fn watch<T, C>(&self, guard: &Arc<Expiring<T, C>>)
+		where T: Protectable + Send + Sync + 'static, C: Clock + 'static {
+		let weak: Weak<dyn Expire> = Arc::downgrade(guard) as Weak<Expiring<T, C>>;
+		self.watched.lock().unwrap_or_else(PoisonError::into_inner).push(weak);
+	}
+
+	/// Number of watched guards
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.watched.lock().unwrap_or_else(PoisonError::into_inner).len()
+	}
+
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Wipe expired guards
+	pub fn reap(&self) {
+		self.watched.lock().unwrap_or_else(PoisonError::into_inner)
+			.retain(|weak| weak.upgrade().is_some_and(|guard| !guard.expire()));
+	}
+}
+
+impl std::fmt::Debug for Reaper {
+	fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		fmt.debug_struct("Reaper").field("watched", &self.len()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::boxed::Box;
+
+	fn secret(clock: &Arc<ManualClock>, ttl: u64) -> Expiring<crate::boxed::InnerBox<u32>, Arc<ManualClock>> {
+		Expiring::with_clock(Box::new(0x55555555), Duration::from_secs(ttl), clock.clone())
+	}
+
+	#[test]
+	fn deadline() {
+		let clock = Arc::new(ManualClock::new());
+		let mut test = secret(&clock, 60);
+
+		*test.borrow_mut() = 0xdeadbeef;
+		clock.advance(Duration::from_secs(59));
+		assert_eq!(*test.borrow(), 0xdeadbeef);
+		assert!(!test.is_wiped());
+
+		clock.advance(Duration::from_secs(1));
+		assert!(test.is_expired());
+		assert!(matches!(test.try_borrow(), Err(BorrowError::Expired)));
+		assert!(matches!(test.try_borrow_mut(), Err(BorrowError::Expired)));
+		assert!(test.is_wiped());
+	}
+
+	#[test]
+	fn borrowed() {
+		let clock = Arc::new(ManualClock::new());
+		let test = secret(&clock, 60);
+
+		let immutable = test.borrow();
+		clock.advance(Duration::from_secs(60));
+		assert!(matches!(test.try_borrow(), Err(BorrowError::Expired)));
+
+		// Outstanding references remain valid
+		assert_eq!(*immutable, 0x55555555);
+		assert!(!test.is_wiped());
+
+		drop(immutable);
+		assert!(test.is_wiped());
+	}
+
+	#[test]
+	fn reap() {
+		let clock = Arc::new(ManualClock::new());
+		let reaper = Reaper::new();
+
+		let short = Arc::new(secret(&clock, 30));
+		let long = Arc::new(secret(&clock, 60));
+		let dropped = Arc::new(secret(&clock, 60));
+
+		reaper.watch(&short);
+		reaper.watch(&long);
+		reaper.watch(&dropped);
+		drop(dropped);
+
+		reaper.reap();
+		assert_eq!(reaper.len(), 2);
+		assert!(!short.is_wiped());
+
+		clock.advance(Duration::from_secs(30));
+		reaper.reap();
+		assert_eq!(reaper.len(), 1);
+		assert!(short.is_wiped());
+		assert!(!long.is_wiped());
+
+		clock.advance(Duration::from_secs(30));
+		reaper.reap();
+		assert!(reaper.is_empty());
+		assert!(long.is_wiped());
+
+		// Frozen guards are wiped unless sealed, and are no longer watched either way
+		let mut frozen = Box::new(0x55555555);
+		frozen.freeze().unwrap();
+		let frozen = Arc::new(Expiring::with_clock(frozen, Duration::from_secs(30), clock.clone()));
+
+		reaper.watch(&frozen);
+		clock.advance(Duration::from_secs(30));
+		reaper.reap();
+		assert!(reaper.is_empty());
+		assert_eq!(frozen.is_wiped(), !frozen.guard.is_sealed());
+	}
+
+	#[test]
+	fn spawn() {
+		let reaper = Reaper::spawn(Duration::from_millis(5));
+		let test = Arc::new(Expiring::new(Box::<u32>::new(0x55555555), Duration::from_millis(20)));
+		reaper.watch(&test);
+
+		let start = Instant::now();
+		while !test.is_wiped() {
+			assert!(start.elapsed() < Duration::from_secs(10));
+			thread::sleep(Duration::from_millis(5));
+		}
+
+		assert!(reaper.is_empty());
+	}
+}
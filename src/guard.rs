@@ -1,138 +1,912 @@
 //! Atomically reference‐counted access guard
 
+use crate::audit::{self, Event, Hook, Kind, Phase};
+use crate::error::{Error, BorrowError};
+use crate::stats::{Counters, Stats};
 use crate::traits::Protectable;
+use crate::wait;
 
-use std::intrinsics::likely;
+use std::cell::UnsafeCell;
+use std::intrinsics::{likely, unlikely};
 use std::fmt;
+use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut, Index, IndexMut, Drop};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::panic::Location;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use std::time::{Instant, SystemTime};
 
 /// Atomically reference‐counted access guard
+///
+/// If a thread panics while holding a [`RefMut`], the guard is poisoned and subsequent borrows fail with
+/// [`BorrowError::Poisoned`], which carries the reference for recovery. Optionally, the contents may be
+/// [wiped on poisoning](Self::set_wipe_on_poison) instead.
+///
+/// Accesses may be reported to an [audit hook](Self::set_audit_hook). With an [access budget](Self::set_budget),
+/// the contents are wiped once the budget is exhausted and the last reference is dropped. Borrows and accessible
+/// time may be [measured](Self::set_stats).
+///
+/// Formatting with [`Debug`](fmt::Debug) never shows the contents, only the state of the guard. Part of the contents
+/// may be shown [masked](crate::redact).
+///
+/// Preferably, the contents are accessed [`with`](Self::with) a closure, so that they are accessible only for the
+/// duration of the call:
+///
+/// ```
+/// use sensitive::boxed::Box;
+///
+/// let mut key = Box::new([0u8; 32]);
+/// key.with_mut(|key| key.fill(0x55));
+/// assert!(key.with(|key| key.iter().all(|&byte| byte == 0x55)));
+/// ```
 #[must_use]
-pub struct Guard<T: Protectable>(AtomicUsize, T);
+pub struct Guard<T: Protectable> {
+	state: AtomicUsize,
+	wipe_on_poison: bool,
+	budget: AtomicUsize,
+	hook: Option<&'static dyn Hook>,
+	location: AtomicPtr<Location<'static>>,
+	counters: Option<std::boxed::Box<Counters>>,
+
+	/// Whether the thread holding the mutable reference was already panicking when acquiring it
+	panicking: AtomicBool,
+
+	frozen: bool,
+	sealed: bool,
+	inner: UnsafeCell<ManuallyDrop<T>>,
+}
+
+unsafe impl<T: Protectable + Send + Sync> Sync for Guard<T> {}
 
 /// Reference to immutably borrowed guarded value
+///
+/// References are only created by borrowing, which makes the contents accessible:
+///
+/// ```compile_fail
+/// use sensitive::boxed::Box;
+/// use sensitive::guard::Ref;
+///
+/// let key = Box::new([0u8; 32]);
+/// let _ = Ref(&key);
+/// ```
 #[must_use]
-pub struct Ref<'t, T: Protectable>(pub &'t Guard<T>);
+pub struct Ref<'t, T: Protectable>(pub(crate) &'t Guard<T>);
 
 /// Reference to mutably borrowed guarded value
 #[must_use]
-pub struct RefMut<'t, T: Protectable>(pub &'t mut Guard<T>);
+pub struct RefMut<'t, T: Protectable>(pub(crate) &'t Guard<T>);
+
+/// Reference to a component of an immutably borrowed guarded value
+///
+/// Created by [`Ref::map`] and related functions. The guarded value remains accessible for the lifetime of the
+/// reference.
+#[must_use]
+pub struct MappedRef<'t, U: ?Sized> {
+	value: &'t U,
+	guard: &'t dyn Anchor,
+}
+
+/// Reference to a component of a mutably borrowed guarded value
+///
+/// Created by [`RefMut::map`] and related functions. The guarded value remains accessible for the lifetime of the
+/// reference.
+#[must_use]
+pub struct MappedRefMut<'t, U: ?Sized> {
+	value: &'t mut U,
+	guard: &'t dyn Anchor,
+}
+
+/// Type‐erased reference management for projected references
+trait Anchor {
+	/// Add immutable reference
+	fn retain(&self);
+
+	/// Drop immutable reference
+	fn dismiss(&self);
+
+	/// Add mutable reference to disjoint component
+	fn retain_mut(&self);
+
+	/// Drop mutable reference
+	fn dismiss_mut(&self);
+}
 
 impl<T: Protectable> Guard<T> {
 	const ACC: usize = usize::MAX / 2 + 1;
-	const REF: usize = !Self::ACC;
+	const BSY: usize = Self::ACC >> 1;
+	const WAI: usize = Self::ACC >> 2;
+	const POI: usize = Self::ACC >> 3;
+	const WIP: usize = Self::ACC >> 4;
+	const FLG: usize = Self::POI | Self::WIP;
+	const REF: usize = !(Self::ACC | Self::BSY | Self::WAI | Self::FLG);
 	const MUT: usize = usize::MAX & Self::REF;
-	const MAX: usize = (usize::MAX & Self::REF) - 1;
+	const MAX: usize = Self::MUT / 2;
+
+	/// Budget of unlimited accesses
+	const UNLIMITED: usize = usize::MAX;
 
 	#[inline]
 	pub fn from_inner(inner: T) -> Self {
-		Self(AtomicUsize::default(), inner)
+		Self {
+			state: AtomicUsize::default(),
+			wipe_on_poison: false,
+			budget: AtomicUsize::new(Self::UNLIMITED),
+			hook: None,
+			location: AtomicPtr::default(),
+			counters: None,
+			panicking: AtomicBool::new(false),
+			frozen: false,
+			sealed: false,
+			inner: UnsafeCell::new(ManuallyDrop::new(inner)),
+		}
+	}
+
+	/// Wait for concurrent protection change by parking on the state
+	fn park(state: &AtomicUsize, refs: usize) -> bool {
+		// Announce waiter
+		if refs & Self::WAI != 0 || state.compare_exchange(refs, refs | Self::WAI, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+			wait::wait(state, refs | Self::WAI);
+		}
+
+		true
 	}
 
-	fn acquire(&self) -> &Self {
-		// Increment ref counter
-		let mut refs = self.0.fetch_add(1, Ordering::AcqRel);
+	/// Wait for concurrent protection change by parking on the state, giving up once `deadline` has passed
+	fn park_until(state: &AtomicUsize, refs: usize, deadline: Instant) -> bool {
+		let timeout = deadline.saturating_duration_since(Instant::now());
 
-		// Panic before overflow
-		assert!(refs & Self::REF < Self::MAX);
+		if timeout.is_zero() {
+			return false;
+		}
 
-		if refs == 0 {
-			// First acquisition
-			self.1.unlock().unwrap();
+		// Announce waiter
+		if refs & Self::WAI != 0 || state.compare_exchange(refs, refs | Self::WAI, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+			wait::wait_timeout(state, refs | Self::WAI, timeout);
+		}
 
-			// Mark accessible
-			self.0.fetch_or(Self::ACC, Ordering::Release);
-		} else {
-			while refs & Self::ACC == 0 {
-				std::thread::yield_now();
-				refs = self.0.load(Ordering::Acquire);
+		true
+	}
+
+	/// Wait for concurrent protection change by yielding
+	#[cfg(test)]
+	fn spin(_: &AtomicUsize, _: usize) -> bool {
+		std::thread::yield_now();
+		true
+	}
+
+	/// Complete protection change
+	fn settle(&self, refs: usize) {
+		if self.state.fetch_update(Ordering::AcqRel, Ordering::Acquire,
+		|state| Some(state & Self::FLG | refs)).unwrap() & Self::WAI != 0 {
+			wait::wake(&self.state);
+		}
+	}
+
+	/// Make contents inaccessible unless sealed
+	#[inline]
+	fn lock(&self) -> Result<(), std::io::Error> {
+		if self.sealed { Ok(()) } else { unsafe { self.inner() }.lock() }
+	}
+
+	/// Make contents read‐only unless sealed
+	#[inline]
+	fn unlock(&self) -> Result<(), std::io::Error> {
+		if self.sealed { Ok(()) } else { unsafe { self.inner() }.unlock() }
+	}
+
+	/// Report change of accessibility to audit hook and counters
+	fn audit(&self, kind: Kind, phase: Phase) {
+		if let Some(counters) = &self.counters {
+			counters.transition(phase);
+		}
+
+		self.report(kind, phase);
+	}
+
+	/// Report event to audit hook only
+	fn report(&self, kind: Kind, phase: Phase) {
+		if let Some(hook) = self.hook.or_else(audit::default_hook) {
+			if let Some(location) = unsafe { self.location.load(Ordering::Relaxed).as_ref() } {
+				hook.record(&Event { kind, phase, location, time: SystemTime::now() });
 			}
 		}
+	}
+
+	/// Count borrow if statistics are enabled
+	#[inline]
+	fn count(&self, kind: Kind) {
+		if let Some(counters) = &self.counters {
+			counters.borrow(kind);
+		}
+	}
+
+	/// Consume access budget, failing if exhausted
+	fn consume(&self) -> bool {
+		self.budget.fetch_update(Ordering::AcqRel, Ordering::Acquire, |budget| match budget {
+			0 | Self::UNLIMITED => None,
+			_ => Some(budget - 1),
+		}) != Err(0)
+	}
 
-		self
+	#[inline] #[track_caller]
+	fn acquire(&self) -> Result<&Self, BorrowError<&Self>> {
+		self.acquire_with(Self::park)
 	}
 
-	fn release(&self) -> &Self {
+	#[track_caller]
+	fn acquire_with<W>(&self, wait: W) -> Result<&Self, BorrowError<&Self>>
+		where W: Fn(&AtomicUsize, usize) -> bool {
+		let mut refs = self.state.load(Ordering::Acquire);
+
+		loop {
+			if unlikely(refs & Self::WIP != 0) {
+				return Err(BorrowError::Wiped);
+			}
+
+			// Fail before overflow
+			if refs & Self::REF == Self::MAX {
+				return Err(BorrowError::Overflow);
+			}
+
+			if likely(refs & Self::ACC != 0 && refs & Self::REF < Self::MAX) {
+				// Increment ref counter
+				match self.state.compare_exchange_weak(refs, refs + 1, Ordering::AcqRel, Ordering::Acquire) {
+					Ok(_) => break,
+					Err(current) => refs = current,
+				}
+			} else if refs & !Self::FLG == 0 {
+				// First acquisition
+				match self.state.compare_exchange_weak(refs, refs | Self::BSY, Ordering::AcqRel, Ordering::Acquire) {
+					Ok(_) => match self.unlock() {
+						Ok(()) => {
+							self.location.store(std::ptr::from_ref(Location::caller()).cast_mut(), Ordering::Relaxed);
+							self.audit(Kind::Immutable, Phase::Acquire);
+
+							// Mark accessible
+							self.settle(Self::ACC | 1);
+							break;
+						},
+						Err(err) => {
+							// Roll back
+							self.settle(0);
+							return Err(err.into());
+						}
+					},
+					Err(current) => refs = current,
+				}
+			} else {
+				// Wait for concurrent protection change or mutable reference
+				if !wait(&self.state, refs) {
+					return Err(BorrowError::Borrowed);
+				}
+
+				refs = self.state.load(Ordering::Acquire);
+			}
+		}
+
+		// Consume budget only while holding a reference, so that the contents are not wiped in between
+		if unlikely(!self.consume()) {
+			let _ = self.release();
+			return Err(BorrowError::Exhausted);
+		}
+
+		self.count(Kind::Immutable);
+
+		if unlikely(refs & Self::POI != 0) {
+			Err(BorrowError::Poisoned(self))
+		} else {
+			Ok(self)
+		}
+	}
+
+	fn release(&self) -> Result<&Self, Error> {
 		// Last release?
-		if self.0.fetch_update(Ordering::AcqRel, Ordering::Acquire,
+		if self.state.fetch_update(Ordering::AcqRel, Ordering::Acquire,
 		|refs| if refs & Self::REF == 1 {
 			// Panic on illegal access modification
 			debug_assert_ne!(refs & Self::ACC, 0);
 
 			// Mark inaccessible
-			Some(refs & !Self::ACC)
+			Some(refs & (Self::WAI | Self::FLG) | Self::BSY)
 		} else {
 			// Panic before underflow
 			debug_assert!(refs & Self::REF > 0);
 
 			// Decrement ref counter
 			Some(refs - 1)
-		}).unwrap() & Self::REF == 1 {  // Last release?
-		self.0.fetch_update(Ordering::AcqRel, Ordering::Acquire,
-		|refs| if likely(refs == 1) {
-				// Last release
-				self.1.lock().unwrap();
+		}).unwrap() & Self::REF == 1 {
+			// Wipe once access budget is exhausted
+			if self.budget.load(Ordering::SeqCst) == 0 && unsafe { self.wipe() }.is_ok() {
+				self.audit(Kind::Immutable, Phase::Release);
+				return Ok(self);
+			}
+
+			if let Err(err) = self.lock() {
+				// Remain accessible
+				self.settle(Self::ACC);
+				return Err(err.into());
+			}
+
+			self.audit(Kind::Immutable, Phase::Release);
+			self.settle(0);
+		}
+
+		Ok(self)
+	}
+
+	#[track_caller]
+	fn acquire_mut(&mut self) -> Result<&Self, BorrowError<&Self>> {
+		let refs = *self.state.get_mut();
+
+		if unlikely(refs & Self::WIP != 0) {
+			return Err(BorrowError::Wiped);
+		}
+
+		// Fail on leaked references
+		if refs & Self::REF != 0 {
+			return Err(BorrowError::Borrowed);
+		}
+
+		if self.frozen {
+			return Err(BorrowError::Frozen);
+		}
+
+		let budget = *self.budget.get_mut();
+		if unlikely(budget == 0) {
+			return Err(BorrowError::Exhausted);
+		}
+
+		*self.state.get_mut() = refs & Self::FLG | Self::ACC | Self::MUT;
+
+		if let Err(err) = self.inner.get_mut().unlock_mut() {
+			*self.state.get_mut() = refs;
+			return Err(err.into());
+		}
+
+		if budget != Self::UNLIMITED {
+			*self.budget.get_mut() = budget - 1;
+		}
+
+		*self.location.get_mut() = std::ptr::from_ref(Location::caller()).cast_mut();
+		*self.panicking.get_mut() = std::thread::panicking();
+		self.audit(Kind::Mutable, Phase::Acquire);
+		self.count(Kind::Mutable);
+
+		if unlikely(refs & Self::POI != 0) {
+			Err(BorrowError::Poisoned(self))
+		} else {
+			Ok(self)
+		}
+	}
+
+	/// Acquire mutable reference through shared reference
+	#[track_caller]
+	fn acquire_shared_mut_with<W>(&self, wait: W) -> Result<&Self, BorrowError<&Self>>
+		where W: Fn(&AtomicUsize, usize) -> bool {
+		if self.frozen {
+			return Err(BorrowError::Frozen);
+		}
+
+		let mut refs = self.state.load(Ordering::Acquire);
+
+		loop {
+			if unlikely(refs & Self::WIP != 0) {
+				return Err(BorrowError::Wiped);
+			}
+
+			if refs & !(Self::WAI | Self::FLG) == 0 {
+				match self.state.compare_exchange_weak(refs, refs | Self::BSY, Ordering::AcqRel, Ordering::Acquire) {
+					Ok(_) => break,
+					Err(current) => refs = current,
+				}
+			} else {
+				// Wait for references to be dropped
+				if !wait(&self.state, refs) {
+					return Err(BorrowError::Borrowed);
+				}
+
+				refs = self.state.load(Ordering::Acquire);
+			}
+		}
+
+		// Exclusive from here
+		if unlikely(!self.consume()) {
+			if unsafe { self.wipe() }.is_err() {
+				self.settle(0);
+			}
+
+			return Err(BorrowError::Exhausted);
+		}
+
+		if let Err(err) = unsafe { &mut *self.inner.get() }.unlock_mut() {
+			// Roll back
+			self.settle(0);
+			return Err(err.into());
+		}
+
+		self.location.store(std::ptr::from_ref(Location::caller()).cast_mut(), Ordering::Relaxed);
+		self.panicking.store(std::thread::panicking(), Ordering::Relaxed);
+		self.audit(Kind::Mutable, Phase::Acquire);
+		self.count(Kind::Mutable);
+		self.settle(Self::ACC | Self::MUT);
+
+		if unlikely(refs & Self::POI != 0) {
+			Err(BorrowError::Poisoned(self))
+		} else {
+			Ok(self)
+		}
+	}
+
+	fn release_mut(&self) -> Result<&Self, Error> {
+		// Mark inaccessible
+		self.state.fetch_update(Ordering::AcqRel, Ordering::Acquire, |refs| {
+			debug_assert_eq!(refs & (Self::ACC | Self::REF), Self::ACC | Self::MUT);
+			Some(refs & (Self::WAI | Self::FLG) | Self::BSY)
+		}).unwrap();
+
+		// Wipe once access budget is exhausted
+		if self.budget.load(Ordering::SeqCst) == 0 && unsafe { self.wipe() }.is_ok() {
+			self.audit(Kind::Mutable, Phase::Release);
+			return Ok(self);
+		}
+
+		if let Err(err) = unsafe { self.inner() }.lock() {
+			// Remain accessible
+			self.settle(Self::ACC);
+			return Err(err.into());
+		}
+
+		self.audit(Kind::Mutable, Phase::Release);
+		self.settle(0);
+		Ok(self)
+	}
+
+	/// Release mutable reference, poisoning if the thread started panicking while holding it
+	fn drop_mut(&self) {
+		if std::thread::panicking() && !self.panicking.load(Ordering::Relaxed) {
+			self.poison();
+		} else {
+			self.release_mut().unwrap();
+		}
+	}
+
+	/// Poison while mutably borrowed
+	fn poison(&self) {
+		self.state.fetch_or(Self::POI, Ordering::AcqRel);
+
+		if self.wipe_on_poison && unsafe { self.wipe() }.is_ok() {
+			self.audit(Kind::Mutable, Phase::Release);
+		} else {
+			let _ = self.release_mut();
+		}
+	}
+
+	/// Drop contents, zeroing and releasing their memory
+	///
+	/// # Safety
+	///
+	/// The caller must have exclusive access to the contents.
+	unsafe fn wipe(&self) -> Result<(), Error> {
+		// Sealed memory can neither be made writable nor released
+		if self.sealed {
+			return Err(Error::Frozen);
+		}
+
+		if self.state.load(Ordering::Acquire) & Self::WIP == 0 {
+			let inner = &mut *self.inner.get();
+
+			// Dropping the contents may require access
+			inner.unlock_mut()?;
+			ManuallyDrop::drop(inner);
+
+			self.settle(Self::WIP);
+		}
+
+		Ok(())
+	}
+
+	#[must_use] #[inline]
+	pub fn is_poisoned(&self) -> bool {
+		self.state.load(Ordering::Acquire) & Self::POI != 0
+	}
+
+	#[must_use] #[inline]
+	pub fn is_wiped(&self) -> bool {
+		self.state.load(Ordering::Acquire) & Self::WIP != 0
+	}
+
+	/// Clear poisoned state after the contents have been recovered
+	#[inline]
+	pub fn clear_poison(&self) {
+		self.state.fetch_and(!Self::POI, Ordering::AcqRel);
+	}
+
+	/// Wipe contents instead of retaining them when the guard is poisoned
+	#[inline]
+	pub fn set_wipe_on_poison(&mut self, wipe: bool) {
+		self.wipe_on_poison = wipe;
+	}
+
+	/// Convert contents, carrying over the configuration of the guard
+	///
+	/// # Panics
+	///
+	/// Panics if the contents have been wiped.
+	pub(crate) fn convert<U, F>(self, convert: F) -> Guard<U>
+		where U: Protectable, F: FnOnce(T) -> U {
+		let mut this = ManuallyDrop::new(self);
+		let refs = *this.state.get_mut();
+
+		assert_eq!(refs & Self::WIP, 0, "contents wiped");
+		debug_assert_eq!(refs & Self::REF, 0);
+
+		let inner = unsafe { ManuallyDrop::take(this.inner.get_mut()) };
+
+		Guard {
+			state: AtomicUsize::new(refs),
+			wipe_on_poison: this.wipe_on_poison,
+			budget: AtomicUsize::new(*this.budget.get_mut()),
+			hook: this.hook,
+			location: AtomicPtr::new(*this.location.get_mut()),
+			counters: this.counters.take(),
+			panicking: AtomicBool::new(false),
+			frozen: this.frozen,
+			sealed: this.sealed,
+			inner: UnsafeCell::new(ManuallyDrop::new(convert(inner))),
+		}
+	}
 
-				Some(0)
+	/// Fail unless the contents may be replaced
+	pub(crate) fn check_idle(&mut self) -> Result<(), Error> {
+		let refs = *self.state.get_mut();
+
+		if refs & Self::WIP != 0 {
+			Err(Error::Wiped)
+		} else if refs & Self::REF != 0 {
+			// Leaked references
+			Err(Error::Borrowed)
+		} else if refs & Self::POI != 0 {
+			// Contents may be torn and must not escape the poisoned guard
+			Err(Error::Poisoned)
+		} else if self.frozen {
+			Err(Error::Frozen)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Swap contents with `other` without copying them
+	///
+	/// Access budgets, audit hooks and statistics remain with their guards. Fails with [`Error::Poisoned`] if either
+	/// guard is poisoned.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn swap(&mut self, other: &mut Self) -> Result<(), Error> {
+		self.check_idle()?;
+		other.check_idle()?;
+
+		std::mem::swap(self.inner.get_mut(), other.inner.get_mut());
+		Ok(())
+	}
+
+	/// Replace contents with those of `other`, wiping the previous contents
+	pub(crate) fn replace_with(&mut self, mut other: Self) -> Result<(), Error> {
+		self.swap(&mut other)?;
+		unsafe { other.wipe() }
+	}
+
+	/// Move contents out of the guard, leaving them accessible
+	///
+	/// Consumes one access of the budget.
+	#[track_caller]
+	pub(crate) fn into_unguarded(mut self) -> Result<T, Error> {
+		match self.acquire_mut() {
+			Ok(_) => (),
+			Err(BorrowError::Poisoned(guard)) => {
+				guard.release_mut()?;
+				return Err(Error::Poisoned);
+			},
+			Err(err) => return Err(err.into()),
+		}
+
+		self.audit(Kind::Mutable, Phase::Release);
+
+		// Prevent dropping the contents along with the guard
+		*self.state.get_mut() = Self::WIP;
+		Ok(unsafe { ManuallyDrop::take(self.inner.get_mut()) })
+	}
+
+	/// Limit the number of remaining borrows
+	///
+	/// Each borrow consumes one access. Once the budget is exhausted, further borrows fail with
+	/// [`BorrowError::Exhausted`] and the contents are wiped when the last reference is dropped. A budget of zero
+	/// wipes the contents immediately. [`None`] lifts the limit.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn set_budget(&mut self, budget: Option<usize>) -> Result<(), Error> {
+		*self.budget.get_mut() = budget.unwrap_or(Self::UNLIMITED);
+
+		if budget == Some(0) && *self.state.get_mut() & Self::REF == 0 {
+			unsafe { self.wipe() }?;
+		}
+
+		Ok(())
+	}
+
+	/// Make contents permanently read‐only
+	///
+	/// Mutable borrows fail with [`Error::Frozen`] afterwards. Where supported, the allocation and its guard pages are
+	/// also sealed with `mseal(2)`, so that their protection can no longer be changed by any means. This comes at a
+	/// cost: sealed contents remain readable for the rest of the process and are never wiped, the allocation is never
+	/// released, and any [tag](crate::alloc::Tag) quota it is accounted to is never refunded. Where sealing is
+	/// unsupported, the contents are still wiped once the access budget is exhausted and when the guard is dropped.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn freeze(&mut self) -> Result<(), Error> {
+		if self.frozen {
+			return Ok(());
+		}
+
+		self.check_idle()?;
+
+		// Leave the guard unchanged if sealing fails
+		self.sealed = self.inner.get_mut().seal()?;
+		self.frozen = true;
+		Ok(())
+	}
+
+	#[must_use] #[inline]
+	pub fn is_frozen(&self) -> bool {
+		self.frozen
+	}
+
+	/// Whether the allocation has been sealed against protection changes
+	#[must_use] #[inline]
+	pub fn is_sealed(&self) -> bool {
+		self.sealed
+	}
+
+	/// Exhaust access budget, wiping the contents once no references remain
+	pub(crate) fn exhaust(&self) {
+		self.budget.store(0, Ordering::SeqCst);
+		let mut refs = self.state.load(Ordering::SeqCst);
+
+		loop {
+			if refs & (Self::ACC | Self::WIP) != 0 {
+				// Wiped on last release
+				break;
+			} else if refs & Self::BSY == 0 {
+				match self.state.compare_exchange_weak(refs, refs | Self::BSY, Ordering::AcqRel, Ordering::Acquire) {
+					Ok(_) => {
+						if unsafe { self.wipe() }.is_err() {
+							self.settle(0);
+						}
+
+						break;
+					},
+					Err(current) => refs = current,
+				}
 			} else {
-				// Panic on illegal access modification
-				debug_assert_eq!(refs & Self::ACC, 0);
+				// Wait for concurrent protection change
+				Self::park(&self.state, refs);
+				refs = self.state.load(Ordering::Acquire);
+			}
+		}
+	}
 
-				// Reacquisition
-				self.1.unlock().unwrap();
+	/// Number of remaining borrows, if limited
+	#[must_use] #[inline]
+	pub fn budget(&self) -> Option<usize> {
+		match self.budget.load(Ordering::Acquire) {
+			Self::UNLIMITED => None,
+			budget => Some(budget),
+		}
+	}
 
-				Some((refs - 1) | Self::ACC)
-			}).unwrap();
+	/// Split off half of the remaining access budget, failing if no access would be left to split off
+	pub(crate) fn split_budget(&self) -> Result<Option<usize>, Error> {
+		match self.budget.fetch_update(Ordering::AcqRel, Ordering::Acquire, |budget| match budget {
+			Self::UNLIMITED => None,
+			_ if budget < 2 => None,
+			_ => Some(budget - budget / 2),
+		}) {
+			Ok(budget) => Ok(Some(budget / 2)),
+			Err(Self::UNLIMITED) => Ok(None),
+			Err(_) => Err(Error::Exhausted),
 		}
+	}
 
-		self
+	/// Report accesses to `hook` instead of the [default hook](audit::set_default_hook)
+	#[inline]
+	pub fn set_audit_hook(&mut self, hook: Option<&'static dyn Hook>) {
+		self.hook = hook;
 	}
 
-	fn acquire_mut(&mut self) -> &mut Self {
-		debug_assert_eq!(self.0.swap(Self::ACC | Self::MUT, Ordering::AcqRel), 0);
-		self.1.unlock_mut().unwrap();
-		self
+	/// Count borrows and measure accessible time
+	///
+	/// Disabling statistics discards the counters.
+	#[inline]
+	pub fn set_stats(&mut self, enabled: bool) {
+		self.counters = enabled.then(|| std::boxed::Box::new(Counters::new()));
+	}
+
+	/// Access statistics, if enabled
+	#[must_use] #[inline]
+	pub fn stats(&self) -> Option<Stats> {
+		self.counters.as_ref().map(|counters| counters.stats())
+	}
+
+	/// Whether the contents are currently accessible
+	#[must_use] #[inline]
+	pub fn is_accessible(&self) -> bool {
+		self.state.load(Ordering::Acquire) & Self::ACC != 0
 	}
 
-	fn release_mut(&mut self) -> &Self {
-		debug_assert_eq!(self.0.swap(0, Ordering::AcqRel), Self::ACC | Self::MUT);
-		self.1.lock().unwrap();
-		self
+	/// Number of immutable references currently held
+	#[must_use] #[inline]
+	pub fn readers(&self) -> usize {
+		match self.state.load(Ordering::Acquire) {
+			refs if refs & Self::ACC != 0 && refs & Self::REF < Self::MAX => refs & Self::REF,
+			_ => 0,
+		}
 	}
 
 	#[inline]
 	pub(crate) unsafe fn inner(&self) -> &T {
-		&self.1
+		&*self.inner.get()
 	}
 
 	#[inline]
 	pub(crate) unsafe fn inner_mut(&mut self) -> &mut T {
-		&mut self.1
+		self.inner.get_mut()
+	}
+
+	pub(crate) fn mutate<M, R>(&mut self, mutation: M) -> R
+		where M: FnOnce(&mut T) -> R {
+		let refs = *self.state.get_mut();
+		debug_assert_eq!(refs & !Self::POI, 0);
+
+		*self.state.get_mut() = refs | Self::ACC | Self::MUT;
+		let result = mutation(self.inner.get_mut());
+		*self.state.get_mut() = refs;
+
+		result
 	}
 
-	pub(crate) fn mutate<M, R>(&mut self, mut mutation: M) -> &Self
-		where M: FnMut(&mut T) -> R {
-		debug_assert_eq!(self.0.swap(Self::ACC | Self::MUT, Ordering::AcqRel), 0);
-		mutation(&mut self.1);
-		debug_assert_eq!(self.0.swap(0, Ordering::AcqRel), Self::ACC | Self::MUT);
-		self
+	/// Immutably borrow guarded value, returning an error on failure
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow(&self) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		self.acquire().map(Ref).map_err(|err| err.map(Ref))
 	}
 
-	#[inline]
+	/// Mutably borrow guarded value, returning an error on failure
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow_mut(&mut self) -> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		self.acquire_mut().map(RefMut).map_err(|err| err.map(RefMut))
+	}
+
+	/// Call `f` with the guarded value, which is accessible only for the duration of the call
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_with<R, F>(&self, f: F) -> Result<R, Error>
+		where F: FnOnce(&T) -> R {
+		Ok(f(self.try_borrow()?.inner()))
+	}
+
+	/// Call `f` with the mutable guarded value, which is accessible only for the duration of the call
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_with_mut<R, F>(&mut self, f: F) -> Result<R, Error>
+		where F: FnOnce(&mut T) -> R {
+		Ok(f(self.try_borrow_mut()?.inner_mut()))
+	}
+
+	/// Call `f` with the guarded value, which is accessible only for the duration of the call
+	///
+	/// This is preferable to [`borrow`](Self::borrow), as the value cannot inadvertently be kept accessible.
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed. See [`try_with`](Self::try_with) for a non‐panicking variant.
+	#[inline] #[track_caller]
+	pub fn with<R, F>(&self, f: F) -> R
+		where F: FnOnce(&T) -> R {
+		self.try_with(f).unwrap()
+	}
+
+	/// Call `f` with the mutable guarded value, which is accessible only for the duration of the call
+	///
+	/// This is preferable to [`borrow_mut`](Self::borrow_mut), as the value cannot inadvertently be kept
+	/// accessible.
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed. See [`try_with_mut`](Self::try_with_mut) for a non‐panicking variant.
+	#[inline] #[track_caller]
+	pub fn with_mut<R, F>(&mut self, f: F) -> R
+		where F: FnOnce(&mut T) -> R {
+		self.try_with_mut(f).unwrap()
+	}
+
+	/// Immutably borrow guarded value, waiting for mutable references until `deadline`
+	#[inline] #[track_caller]
+	pub(crate) fn try_borrow_until(&self, deadline: Option<Instant>) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		match deadline {
+			Some(deadline) => self.acquire_with(|state, refs| Self::park_until(state, refs, deadline)),
+			None => self.acquire(),
+		}.map(Ref).map_err(|err| err.map(Ref))
+	}
+
+	/// Mutably borrow guarded value through shared reference, waiting for other references until `deadline`
+	#[inline] #[track_caller]
+	pub(crate) fn try_borrow_shared_mut_until(&self, deadline: Option<Instant>)
+		-> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		match deadline {
+			Some(deadline) => self.acquire_shared_mut_with(|state, refs| Self::park_until(state, refs, deadline)),
+			None => self.acquire_shared_mut_with(Self::park),
+		}.map(RefMut).map_err(|err| err.map(RefMut))
+	}
+
+	/// Immutably borrow guarded value
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed. See [`try_borrow`](Self::try_borrow) for a non‐panicking variant.
+	#[inline] #[track_caller]
 	pub fn borrow(&self) -> Ref<'_, T> {
-		Ref(self.acquire())
+		self.try_borrow().unwrap()
 	}
 
-	#[inline]
+	/// Mutably borrow guarded value
+	///
+	/// # Panics
+	///
+	/// Panics if the value cannot be borrowed. See [`try_borrow_mut`](Self::try_borrow_mut) for a non‐panicking
+	/// variant.
+	#[inline] #[track_caller]
 	pub fn borrow_mut(&mut self) -> RefMut<'_, T> {
-		RefMut(self.acquire_mut())
+		self.try_borrow_mut().unwrap()
+	}
+}
+
+impl<T: Protectable> Anchor for Guard<T> {
+	fn retain(&self) {
+		let refs = self.state.fetch_add(1, Ordering::AcqRel);
+
+		// Panic before overflow
+		assert!(refs & Self::REF < Self::MAX);
+		debug_assert_ne!(refs & Self::ACC, 0);
+	}
+
+	fn dismiss(&self) {
+		self.release().unwrap();
+	}
+
+	fn retain_mut(&self) {
+		// Mutable references are counted down from MUT
+		assert!(self.state.fetch_update(Ordering::AcqRel, Ordering::Acquire,
+		|refs| (refs & Self::REF > Self::MAX + 1).then(|| refs - 1)).is_ok());
+	}
+
+	fn dismiss_mut(&self) {
+		if self.state.fetch_update(Ordering::AcqRel, Ordering::Acquire,
+		|refs| (refs & Self::REF != Self::MUT).then(|| refs + 1)).is_err() {
+			self.drop_mut();
+		}
+	}
+}
+
+impl<T: Protectable> Drop for Guard<T> {
+	fn drop(&mut self) {
+		// Sealed memory cannot be released
+		if *self.state.get_mut() & Self::WIP == 0 && !self.sealed {
+			let inner = self.inner.get_mut();
+
+			// Dropping the contents may require access, so leak them otherwise
+			if inner.unlock_mut().is_ok() {
+				unsafe { ManuallyDrop::drop(inner); }
+			}
+		}
 	}
 }
 
 impl<T: Protectable> fmt::Debug for Guard<T> {
 	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let refs = self.0.load(Ordering::Acquire);
+		let refs = self.state.load(Ordering::Acquire);
 
 		write!(fmt, "Guard(")?;
 		if refs & Self::ACC != 0 {
@@ -145,6 +919,14 @@ impl<T: Protectable> fmt::Debug for Guard<T> {
 			write!(fmt, "{}", refs & Self::REF)?;
 		}
 
+		if refs & Self::POI != 0 {
+			write!(fmt, " POI")?;
+		}
+
+		if refs & Self::WIP != 0 {
+			write!(fmt, " WIP")?;
+		}
+
 		write!(fmt, ", {})", std::any::type_name::<T>())
 	}
 }
@@ -152,7 +934,94 @@ impl<T: Protectable> fmt::Debug for Guard<T> {
 impl<T: Protectable> Ref<'_, T> {
 	#[inline]
 	pub fn inner(&self) -> &T {
-		&self.0.1
+		unsafe { self.0.inner() }
+	}
+
+	#[must_use] #[inline]
+	pub fn is_poisoned(&self) -> bool {
+		self.0.is_poisoned()
+	}
+
+	/// Clear poisoned state of the guard after the contents have been recovered
+	#[inline]
+	pub fn clear_poison(&self) {
+		self.0.clear_poison();
+	}
+}
+
+impl<'t, T: Protectable + Deref> Ref<'t, T> {
+	/// Create reference to a component of the borrowed value
+	pub fn map<U: ?Sized, F>(orig: Self, map: F) -> MappedRef<'t, U>
+		where F: FnOnce(&T::Target) -> &U {
+		let guard = orig.0;
+		std::mem::forget(orig);
+
+		MappedRef { value: map(unsafe { guard.inner() }), guard }
+	}
+
+	/// Create reference to an optional component of the borrowed value
+	///
+	/// # Errors
+	///
+	/// Returns the original reference if `map` returns [`None`].
+	pub fn filter_map<U: ?Sized, F>(orig: Self, map: F) -> Result<MappedRef<'t, U>, Self>
+		where F: FnOnce(&T::Target) -> Option<&U> {
+		let guard = orig.0;
+
+		match map(unsafe { guard.inner() }) {
+			Some(value) => {
+				std::mem::forget(orig);
+				Ok(MappedRef { value, guard })
+			},
+			None => Err(orig),
+		}
+	}
+
+	/// Split reference into references to two components of the borrowed value
+	pub fn map_split<U: ?Sized, V: ?Sized, F>(orig: Self, map: F) -> (MappedRef<'t, U>, MappedRef<'t, V>)
+		where F: FnOnce(&T::Target) -> (&U, &V) {
+		let guard = orig.0;
+		std::mem::forget(orig);
+
+		guard.retain();
+		let (u, v) = map(unsafe { guard.inner() });
+
+		(MappedRef { value: u, guard }, MappedRef { value: v, guard })
+	}
+}
+
+impl<'t, T: Protectable + DerefMut> Ref<'t, T> {
+	/// Upgrade unique reference to a mutable reference, making the contents writable in place
+	///
+	/// Concurrent borrows block until the mutable reference is dropped. As the guard itself remains shared, the
+	/// mutable reference is to the borrowed value only, which cannot be reallocated.
+	///
+	/// # Errors
+	///
+	/// Returns the original reference if other references exist or the memory protection cannot be changed.
+	pub fn try_upgrade(orig: Self) -> Result<MappedRefMut<'t, T::Target>, Self> {
+		let guard = orig.0;
+
+		if guard.frozen || guard.state.fetch_update(Ordering::AcqRel, Ordering::Acquire,
+		|refs| (refs & Guard::<T>::REF == 1).then_some(refs & !Guard::<T>::REF | Guard::<T>::MUT)).is_err() {
+			return Err(orig);
+		}
+
+		// Exclusive access from here
+		if unsafe { &mut *guard.inner.get() }.unlock_mut().is_err() {
+			// Roll back
+			guard.settle(Guard::<T>::ACC | 1);
+			return Err(orig);
+		}
+
+		guard.panicking.store(std::thread::panicking(), Ordering::Relaxed);
+
+		// Contents remain accessible, but the kind of borrow changes
+		guard.report(Kind::Immutable, Phase::Release);
+		guard.report(Kind::Mutable, Phase::Acquire);
+
+		std::mem::forget(orig);
+		Ok(MappedRefMut { value: unsafe { &mut *guard.inner.get() }, guard })
 	}
 }
 
@@ -161,7 +1030,7 @@ impl<T: Protectable + Deref> Deref for Ref<'_, T> {
 
 	#[inline]
 	fn deref(&self) -> &Self::Target {
-		&self.0.1
+		unsafe { self.0.inner() }
 	}
 }
 
@@ -170,32 +1039,110 @@ impl<T: Protectable + Index<I>, I> Index<I> for Ref<'_, T> {
 
 	#[inline]
 	fn index(&self, index: I) -> &Self::Output {
-		&self.0.1[index]
+		&(unsafe { self.0.inner() })[index]
 	}
 }
 
 impl<T: Protectable> Drop for Ref<'_, T> {
 	#[inline]
 	fn drop(&mut self) {
-		self.0.release();
+		self.0.release().unwrap();
 	}
 }
 
-impl<T: Protectable + fmt::Debug> fmt::Debug for Ref<'_, T> {
+impl<T: Protectable> fmt::Debug for Ref<'_, T> {
 	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
 		fmt.debug_tuple("Ref").field(&self.0).finish()
 	}
 }
 
-impl<T: Protectable> RefMut<'_, T> {
+impl<'t, T: Protectable> RefMut<'t, T> {
+	#[must_use] #[inline]
+	pub fn is_poisoned(&self) -> bool {
+		self.0.is_poisoned()
+	}
+
+	/// Clear poisoned state of the guard after the contents have been recovered
+	#[inline]
+	pub fn clear_poison(&self) {
+		self.0.clear_poison();
+	}
+
 	#[inline]
 	pub fn inner(&self) -> &T {
-		&self.0.1
+		unsafe { self.0.inner() }
 	}
 
 	#[inline]
 	pub fn inner_mut(&mut self) -> &mut T {
-		&mut self.0.1
+		// Exclusive while mutably borrowed
+		unsafe { &mut *self.0.inner.get() }
+	}
+
+	fn into_raw(orig: Self) -> &'t Guard<T> {
+		let guard = unsafe { std::ptr::read(&orig.0) };
+		std::mem::forget(orig);
+		guard
+	}
+
+	/// Downgrade to an immutable reference, making the contents read‐only in place
+	///
+	/// Unlike dropping the mutable reference and borrowing again, the contents remain accessible throughout.
+	///
+	/// # Panics
+	///
+	/// Panics if the memory protection cannot be changed, after the guard has been released.
+	pub fn downgrade(orig: Self) -> Ref<'t, T> {
+		if let Err(err) = orig.inner().unlock() {
+			drop(orig);
+			panic!("downgrade failed: {err}");
+		}
+
+		let guard = Self::into_raw(orig);
+		debug_assert_eq!(guard.state.load(Ordering::Acquire) & (Guard::<T>::ACC | Guard::<T>::REF), Guard::<T>::ACC | Guard::<T>::MUT);
+		// Contents remain accessible, but the kind of borrow changes
+		guard.report(Kind::Mutable, Phase::Release);
+		guard.report(Kind::Immutable, Phase::Acquire);
+		guard.settle(Guard::<T>::ACC | 1);
+
+		Ref(guard)
+	}
+}
+
+impl<'t, T: Protectable + Deref + DerefMut> RefMut<'t, T> {
+	/// Create reference to a component of the borrowed value
+	pub fn map<U: ?Sized, F>(orig: Self, map: F) -> MappedRefMut<'t, U>
+		where F: FnOnce(&mut T::Target) -> &mut U {
+		let guard = Self::into_raw(orig);
+		let value = map(unsafe { &mut *guard.inner.get() });
+
+		MappedRefMut { value, guard }
+	}
+
+	/// Create reference to an optional component of the borrowed value
+	///
+	/// # Errors
+	///
+	/// Returns the original reference if `map` returns [`None`].
+	pub fn filter_map<U: ?Sized, F>(orig: Self, map: F) -> Result<MappedRefMut<'t, U>, Self>
+		where F: FnOnce(&mut T::Target) -> Option<&mut U> {
+		let guard = Self::into_raw(orig);
+
+		match map(unsafe { &mut *guard.inner.get() }) {
+			Some(value) => Ok(MappedRefMut { value, guard }),
+			None => Err(RefMut(guard)),
+		}
+	}
+
+	/// Split reference into references to two disjoint components of the borrowed value
+	pub fn map_split<U: ?Sized, V: ?Sized, F>(orig: Self, map: F) -> (MappedRefMut<'t, U>, MappedRefMut<'t, V>)
+		where F: FnOnce(&mut T::Target) -> (&mut U, &mut V) {
+		let guard = Self::into_raw(orig);
+
+		guard.retain_mut();
+		let (u, v) = map(unsafe { &mut *guard.inner.get() });
+
+		(MappedRefMut { value: u, guard }, MappedRefMut { value: v, guard })
 	}
 }
 
@@ -204,7 +1151,7 @@ impl<T: Protectable + Deref> Deref for RefMut<'_, T> {
 
 	#[inline]
 	fn deref(&self) -> &Self::Target {
-		&self.0.1
+		unsafe { self.0.inner() }
 	}
 }
 
@@ -213,39 +1160,175 @@ impl<T: Protectable + Index<I>, I> Index<I> for RefMut<'_, T> {
 
 	#[inline]
 	fn index(&self, index: I) -> &Self::Output {
-		&self.0.1[index]
+		&(unsafe { self.0.inner() })[index]
 	}
 }
 
 impl<T: Protectable + Index<I> + IndexMut<I>, I> IndexMut<I> for RefMut<'_, T> {
 	#[inline]
 	fn index_mut(&mut self, index: I) -> &mut Self::Output {
-		&mut self.0.1[index]
+		&mut (unsafe { &mut *self.0.inner.get() })[index]
 	}
 }
 
 impl<T: Protectable + Deref + DerefMut> DerefMut for RefMut<'_, T> {
 	#[inline]
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		&mut self.0.1
+		unsafe { &mut *self.0.inner.get() }
 	}
 }
 
 impl<T: Protectable> Drop for RefMut<'_, T> {
 	#[inline]
 	fn drop(&mut self) {
-		self.0.release_mut();
+		self.0.drop_mut();
 	}
 }
 
-impl<T: Protectable + fmt::Debug> fmt::Debug for RefMut<'_, T> {
+impl<T: Protectable> fmt::Debug for RefMut<'_, T> {
 	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
 		fmt.debug_tuple("RefMut").field(&self.0).finish()
 	}
 }
 
+impl<'t, U: ?Sized> MappedRef<'t, U> {
+	/// Create reference to a component of the referenced value
+	pub fn map<V: ?Sized, F>(orig: Self, map: F) -> MappedRef<'t, V>
+		where F: FnOnce(&U) -> &V {
+		let (value, guard) = (orig.value, orig.guard);
+		std::mem::forget(orig);
+
+		MappedRef { value: map(value), guard }
+	}
+
+	/// Create reference to an optional component of the referenced value
+	///
+	/// # Errors
+	///
+	/// Returns the original reference if `map` returns [`None`].
+	pub fn filter_map<V: ?Sized, F>(orig: Self, map: F) -> Result<MappedRef<'t, V>, Self>
+		where F: FnOnce(&U) -> Option<&V> {
+		let guard = orig.guard;
+
+		match map(orig.value) {
+			Some(value) => {
+				std::mem::forget(orig);
+				Ok(MappedRef { value, guard })
+			},
+			None => Err(orig),
+		}
+	}
+
+	/// Split reference into references to two components of the referenced value
+	pub fn map_split<V: ?Sized, W: ?Sized, F>(orig: Self, map: F) -> (MappedRef<'t, V>, MappedRef<'t, W>)
+		where F: FnOnce(&U) -> (&V, &W) {
+		let (value, guard) = (orig.value, orig.guard);
+		std::mem::forget(orig);
+
+		guard.retain();
+		let (v, w) = map(value);
+
+		(MappedRef { value: v, guard }, MappedRef { value: w, guard })
+	}
+}
+
+impl<U: ?Sized> Deref for MappedRef<'_, U> {
+	type Target = U;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.value
+	}
+}
+
+impl<U: ?Sized> Drop for MappedRef<'_, U> {
+	#[inline]
+	fn drop(&mut self) {
+		self.guard.dismiss();
+	}
+}
+
+impl<U: ?Sized> fmt::Debug for MappedRef<'_, U> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(fmt, "MappedRef({})", std::any::type_name::<U>())
+	}
+}
+
+impl<'t, U: ?Sized> MappedRefMut<'t, U> {
+	fn into_raw(orig: Self) -> (*mut U, &'t dyn Anchor) {
+		let raw = (&raw mut *orig.value, orig.guard);
+		std::mem::forget(orig);
+		raw
+	}
+
+	/// Create reference to a component of the referenced value
+	pub fn map<V: ?Sized, F>(orig: Self, map: F) -> MappedRefMut<'t, V>
+		where F: FnOnce(&mut U) -> &mut V {
+		let (value, guard) = Self::into_raw(orig);
+
+		MappedRefMut { value: map(unsafe { &mut *value }), guard }
+	}
+
+	/// Create reference to an optional component of the referenced value
+	///
+	/// # Errors
+	///
+	/// Returns the original reference if `map` returns [`None`].
+	pub fn filter_map<V: ?Sized, F>(orig: Self, map: F) -> Result<MappedRefMut<'t, V>, Self>
+		where F: FnOnce(&mut U) -> Option<&mut V> {
+		let (value, guard) = Self::into_raw(orig);
+
+		match map(unsafe { &mut *value }) {
+			Some(value) => Ok(MappedRefMut { value, guard }),
+			None => Err(MappedRefMut { value: unsafe { &mut *value }, guard }),
+		}
+	}
+
+	/// Split reference into references to two disjoint components of the referenced value
+	pub fn map_split<V: ?Sized, W: ?Sized, F>(orig: Self, map: F) -> (MappedRefMut<'t, V>, MappedRefMut<'t, W>)
+		where F: FnOnce(&mut U) -> (&mut V, &mut W) {
+		let (value, guard) = Self::into_raw(orig);
+
+		guard.retain_mut();
+		let (v, w) = map(unsafe { &mut *value });
+
+		(MappedRefMut { value: v, guard }, MappedRefMut { value: w, guard })
+	}
+}
+
+impl<U: ?Sized> Deref for MappedRefMut<'_, U> {
+	type Target = U;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.value
+	}
+}
+
+impl<U: ?Sized> DerefMut for MappedRefMut<'_, U> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.value
+	}
+}
+
+impl<U: ?Sized> Drop for MappedRefMut<'_, U> {
+	#[inline]
+	fn drop(&mut self) {
+		self.guard.dismiss_mut();
+	}
+}
+
+impl<U: ?Sized> fmt::Debug for MappedRefMut<'_, U> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(fmt, "MappedRefMut({})", std::any::type_name::<U>())
+	}
+}
+
 #[cfg(test)]
 mod tests {
+	extern crate test;
+
 	use super::*;
 	use std::io::Error;
 
@@ -265,18 +1348,35 @@ mod tests {
 		}
 	}
 
+	struct Faulty(bool);
+
+	impl Protectable for Faulty {
+		fn lock(&self) -> Result<(), Error> {
+			Ok(())
+		}
+
+		fn unlock(&self) -> Result<(), Error> {
+			if self.0 { Ok(()) } else { Err(Error::from(std::io::ErrorKind::PermissionDenied)) }
+		}
+
+		fn unlock_mut(&mut self) -> Result<(), Error> {
+			self.unlock()
+		}
+	}
+
 	#[test]
 	#[should_panic]
 	fn underflow() {
 		let guard = Guard::from_inner(Dummy);
-		guard.release();
+		guard.release().unwrap();
 	}
 
 	#[test]
 	#[should_panic]
 	fn overflow() {
-		let guard = Guard(AtomicUsize::new(Guard::<Dummy>::MAX), Dummy);
-		guard.acquire();
+		let mut guard = Guard::from_inner(Dummy);
+		*guard.state.get_mut() = Guard::<Dummy>::MAX;
+		guard.acquire().unwrap();
 	}
 
 	#[test]
@@ -284,11 +1384,11 @@ mod tests {
 		let guard = Guard::from_inner(Dummy);
 
 		for _ in 0..1024 {
-			guard.acquire();
+			guard.acquire().unwrap();
 		}
 
 		for _ in 0..1024 {
-			guard.release();
+			guard.release().unwrap();
 		}
 	}
 
@@ -297,8 +1397,8 @@ mod tests {
 		let mut guard = Guard::from_inner(Dummy);
 
 		for _ in 0..1024 {
-			guard.acquire_mut();
-			guard.release_mut();
+			guard.acquire_mut().unwrap();
+			guard.release_mut().unwrap();
 		}
 	}
 
@@ -306,8 +1406,283 @@ mod tests {
 	#[should_panic]
 	fn mutable_multiple() {
 		let mut guard = Guard::from_inner(Dummy);
-		guard.acquire_mut();
-		guard.acquire_mut();
+		guard.acquire_mut().unwrap();
+		guard.acquire_mut().unwrap();
+	}
+
+	#[test]
+	fn faulty() {
+		let mut guard = Guard::from_inner(Faulty(false));
+
+		assert!(matches!(guard.try_borrow(), Err(BorrowError::Protect(_))));
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+
+		assert!(matches!(guard.try_borrow_mut(), Err(BorrowError::Protect(_))));
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+
+		guard.inner.get_mut().0 = true;
+		assert!(guard.try_borrow().is_ok());
+		assert!(guard.try_borrow_mut().is_ok());
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[test]
+	fn try_borrow() {
+		let mut guard = Guard::from_inner(Dummy);
+		*guard.state.get_mut() = Guard::<Dummy>::MAX;
+		assert!(matches!(guard.try_borrow(), Err(BorrowError::Overflow)));
+
+		*guard.state.get_mut() = Guard::<Dummy>::ACC | 1;
+		assert!(matches!(guard.try_borrow_mut(), Err(BorrowError::Borrowed)));
+
+		*guard.state.get_mut() = 0;
+		assert!(guard.try_borrow().is_ok());
+		assert!(guard.try_borrow_mut().is_ok());
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[test]
+	fn poison() {
+		let mut guard = Guard::from_inner(Dummy);
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let _ref = guard.borrow_mut();
+			panic!();
+		})).is_err());
+
+		assert!(guard.is_poisoned());
+		assert!(!guard.is_wiped());
+
+		match guard.try_borrow_mut() {
+			Err(BorrowError::Poisoned(recovered)) => {
+				assert!(recovered.is_poisoned());
+				recovered.clear_poison();
+			},
+			_ => panic!(),
+		}
+
+		assert!(!guard.is_poisoned());
+		assert!(guard.try_borrow().is_ok());
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[test]
+	fn poison_unwinding() {
+		struct Bomb<'t>(&'t mut Guard<Dummy>);
+
+		impl Drop for Bomb<'_> {
+			fn drop(&mut self) {
+				// Borrowed and released cleanly while unwinding
+				drop(self.0.borrow_mut());
+			}
+		}
+
+		let mut guard = Guard::from_inner(Dummy);
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let _bomb = Bomb(&mut guard);
+			panic!();
+		})).is_err());
+
+		assert!(!guard.is_poisoned());
+		assert!(guard.try_borrow_mut().is_ok());
+	}
+
+	#[test]
+	fn poison_wipe() {
+		use std::sync::Arc;
+
+		struct Counted(Arc<AtomicUsize>);
+
+		impl Protectable for Counted {
+			fn lock(&self) -> Result<(), Error> {
+				Ok(())
+			}
+
+			fn unlock(&self) -> Result<(), Error> {
+				Ok(())
+			}
+
+			fn unlock_mut(&mut self) -> Result<(), Error> {
+				Ok(())
+			}
+		}
+
+		impl Drop for Counted {
+			fn drop(&mut self) {
+				self.0.fetch_add(1, Ordering::Relaxed);
+			}
+		}
+
+		let drops = Arc::new(AtomicUsize::new(0));
+		let mut guard = Guard::from_inner(Counted(drops.clone()));
+		guard.set_wipe_on_poison(true);
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let _ref = guard.borrow_mut();
+			panic!();
+		})).is_err());
+
+		assert!(guard.is_poisoned());
+		assert!(guard.is_wiped());
+		assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+		assert!(matches!(guard.try_borrow(), Err(BorrowError::Wiped)));
+		assert!(matches!(guard.try_borrow_mut(), Err(BorrowError::Wiped)));
+
+		drop(guard);
+		assert_eq!(drops.load(Ordering::Relaxed), 1);
+	}
+
+	#[derive(Debug)]
+	struct Value(u32);
+
+	impl Protectable for Value {
+		fn lock(&self) -> Result<(), Error> {
+			Ok(())
+		}
+
+		fn unlock(&self) -> Result<(), Error> {
+			Ok(())
+		}
+
+		fn unlock_mut(&mut self) -> Result<(), Error> {
+			Ok(())
+		}
+	}
+
+	impl Deref for Value {
+		type Target = u32;
+
+		fn deref(&self) -> &u32 {
+			&self.0
+		}
+	}
+
+	impl DerefMut for Value {
+		fn deref_mut(&mut self) -> &mut u32 {
+			&mut self.0
+		}
+	}
+
+	#[test]
+	fn downgrade() {
+		let mut guard = Guard::from_inner(Value(0));
+
+		{
+			let mut mutable = guard.borrow_mut();
+			*mutable = 0x55555555;
+
+			let immutable = RefMut::downgrade(mutable);
+			assert_eq!(immutable.0.state.load(Ordering::Acquire), Guard::<Value>::ACC | 1);
+			assert_eq!(*immutable, 0x55555555);
+
+			let other = immutable.0.borrow();
+			assert_eq!(other.0.state.load(Ordering::Acquire), Guard::<Value>::ACC | 2);
+		}
+
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[test]
+	fn upgrade() {
+		let guard = Guard::from_inner(Value(0));
+
+		let first = guard.borrow();
+		let second = guard.borrow();
+
+		// Not unique
+		let first = Ref::try_upgrade(first).unwrap_err();
+		assert_eq!(guard.state.load(Ordering::Acquire), Guard::<Value>::ACC | 2);
+		drop(second);
+
+		let mut mutable = Ref::try_upgrade(first).unwrap();
+		assert_eq!(guard.state.load(Ordering::Acquire), Guard::<Value>::ACC | Guard::<Value>::MUT);
+		*mutable = 0x55555555;
+		drop(mutable);
+
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+		assert_eq!(*guard.borrow(), 0x55555555);
+	}
+
+	#[test]
+	fn upgrade_concurrent() {
+		use std::sync::Barrier;
+		use std::thread;
+
+		let guard = Guard::from_inner(Value(0));
+		let barrier = Barrier::new(2);
+
+		thread::scope(|scope| {
+			let mut mutable = Ref::try_upgrade(guard.borrow()).unwrap();
+
+			let reader = scope.spawn(|| {
+				barrier.wait();
+
+				// Blocks until the upgraded reference is dropped
+				*guard.borrow()
+			});
+
+			barrier.wait();
+			thread::sleep(std::time::Duration::from_millis(10));
+			*mutable = 0x55555555;
+			drop(mutable);
+
+			assert_eq!(reader.join().unwrap(), 0x55555555);
+		});
+
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[test]
+	fn budget() {
+		let guard = {
+			let mut guard = Guard::from_inner(Dummy);
+			guard.set_budget(Some(2)).unwrap();
+			guard
+		};
+
+		let first = guard.borrow();
+		let second = guard.borrow();
+		assert_eq!(guard.budget(), Some(0));
+		assert!(matches!(guard.try_borrow(), Err(BorrowError::Exhausted)));
+
+		drop(first);
+		assert!(!guard.is_wiped());
+		drop(second);
+		assert!(guard.is_wiped());
+		assert!(matches!(guard.try_borrow(), Err(BorrowError::Wiped)));
+
+		let mut guard = Guard::from_inner(Dummy);
+		guard.set_budget(Some(1)).unwrap();
+		drop(guard.borrow_mut());
+		assert!(guard.is_wiped());
+
+		let mut guard = Guard::from_inner(Dummy);
+		guard.set_budget(Some(0)).unwrap();
+		assert!(guard.is_wiped());
+
+		let mut guard = Guard::from_inner(Dummy);
+		guard.set_budget(Some(1)).unwrap();
+		guard.set_budget(None).unwrap();
+		drop(guard.borrow());
+		drop(guard.borrow_mut());
+		assert_eq!(guard.budget(), None);
+		assert!(!guard.is_wiped());
+	}
+
+	#[test]
+	fn with() {
+		let mut guard = Guard::from_inner(Value(0));
+
+		guard.with_mut(|value| value.0 = 0x55555555);
+		assert_eq!(guard.with(|value| **value), 0x55555555);
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+
+		guard.set_budget(Some(1)).unwrap();
+		assert_eq!(guard.try_with(|value| **value).unwrap(), 0x55555555);
+		assert!(matches!(guard.try_with(|value| **value), Err(crate::error::Error::Wiped)));
+		assert!(matches!(guard.try_with_mut(|value| **value), Err(crate::error::Error::Wiped)));
 	}
 
 	#[test]
@@ -324,7 +1699,7 @@ mod tests {
 			}
 		}
 
-		assert_eq!(guard.0.into_inner(), 0);
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
 	}
 
 	#[test]
@@ -335,7 +1710,7 @@ mod tests {
 			let mut _ref = guard.borrow_mut();
 		}
 
-		assert_eq!(guard.0.into_inner(), 0);
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
 	}
 
 	#[test]
@@ -369,6 +1744,60 @@ mod tests {
 			thread.join().unwrap();
 		}
 
-		assert_eq!(Arc::try_unwrap(guard).unwrap().0.into_inner(), 0);
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	/// Simulates the latency of protection changes
+	struct Slow;
+
+	impl Protectable for Slow {
+		fn lock(&self) -> Result<(), Error> {
+			std::thread::sleep(std::time::Duration::from_micros(20));
+			Ok(())
+		}
+
+		fn unlock(&self) -> Result<(), Error> {
+			std::thread::sleep(std::time::Duration::from_micros(20));
+			Ok(())
+		}
+
+		fn unlock_mut(&mut self) -> Result<(), Error> {
+			self.unlock()
+		}
+	}
+
+	fn contention<W>(bench: &mut test::Bencher, wait: W)
+		where W: Fn(&AtomicUsize, usize) -> bool + Copy + Send + Sync {
+		use std::cmp::max;
+		use std::thread;
+
+		const LIMIT: usize = 256;
+
+		let guard = Guard::from_inner(Slow);
+		let concurrency = max(16, 2 * thread::available_parallelism().unwrap().get());
+
+		bench.iter(|| thread::scope(|scope| {
+			for _ in 0..concurrency {
+				scope.spawn(|| {
+					for _ in 0..LIMIT {
+						guard.acquire_with(wait).unwrap();
+						thread::yield_now();
+						guard.release().unwrap();
+					}
+				});
+			}
+		}));
+
+		assert_eq!(guard.state.load(Ordering::Acquire), 0);
+	}
+
+	#[bench]
+	fn contention_park(bench: &mut test::Bencher) {
+		contention(bench, Guard::<Slow>::park);
+	}
+
+	#[bench]
+	fn contention_spin(bench: &mut test::Bencher) {
+		contention(bench, Guard::<Slow>::spin);
 	}
 }
@@ -0,0 +1,357 @@
+//! Guarded fixed‐size keys
+//!
+//! Keys are guarded byte arrays. They are compared in constant time and may be generated randomly or imported from
+//! and exported to hexadecimal and base64 directly in guarded memory. Encoding and decoding do not branch on or
+//! index by the key material.
+
+use crate::auxiliary::zero;
+use crate::boxed::Box;
+use crate::error::Error;
+
+use std::hint::black_box;
+use std::mem::MaybeUninit;
+
+#[cfg(feature = "string")]
+use crate::string::String;
+
+#[cfg(feature = "string")]
+use crate::vec::Vec;
+
+/// Guarded key of `N` bytes
+pub type Key<const N: usize> = Box<[u8; N]>;
+
+/// Mask of all ones if `a < b`, for `a` and `b` less than 256 apart
+#[inline]
+const fn lt(a: i16, b: i16) -> i16 {
+	(a - b) >> 8
+}
+
+#[inline]
+const fn eq(a: i16, b: i16) -> i16 {
+	!lt(a, b) & !lt(b, a)
+}
+
+#[cfg(feature = "string")]
+#[inline]
+fn encode_hex(value: u8) -> u8 {
+	let value = i16::from(value);
+	(value + 0x30 + (lt(9, value) & 0x27)) as u8
+}
+
+/// Decode hexadecimal digit, returning its value and a mask of all ones if valid
+#[inline]
+fn decode_hex(ch: u8) -> (u8, i16) {
+	let ch = i16::from(ch);
+	let lower = ch | 0x20;
+
+	let digit = !lt(ch, 0x30) & lt(ch, 0x3a);
+	let alpha = !lt(lower, 0x61) & lt(lower, 0x67);
+
+	(((ch - 0x30) & digit | (lower - 0x57) & alpha) as u8, digit | alpha)
+}
+
+#[cfg(feature = "string")]
+#[inline]
+fn encode_base64(value: u8) -> u8 {
+	let value = i16::from(value);
+
+	(lt(value, 26) & (value + 0x41)
+		| !lt(value, 26) & lt(value, 52) & (value + 0x61 - 26)
+		| !lt(value, 52) & lt(value, 62) & (value + 0x30 - 52)
+		| eq(value, 62) & 0x2b
+		| eq(value, 63) & 0x2f) as u8
+}
+
+/// Decode base64 character, returning its value and a mask of all ones if valid
+#[inline]
+fn decode_base64(ch: u8) -> (u8, i16) {
+	let ch = i16::from(ch);
+
+	let upper = !lt(ch, 0x41) & lt(ch, 0x5b);
+	let lower = !lt(ch, 0x61) & lt(ch, 0x7b);
+	let digit = !lt(ch, 0x30) & lt(ch, 0x3a);
+	let plus = eq(ch, 0x2b);
+	let slash = eq(ch, 0x2f);
+
+	(((ch - 0x41) & upper
+		| (ch - 0x61 + 26) & lower
+		| (ch - 0x30 + 52) & digit
+		| 62 & plus
+		| 63 & slash) as u8, upper | lower | digit | plus | slash)
+}
+
+/// Length of base64 encoding of `len` bytes including padding
+const fn base64_len(len: usize) -> usize {
+	len.div_ceil(3) * 4
+}
+
+impl<const N: usize> Box<[u8; N]> {
+	/// Move `source` into guarded memory, clearing it out
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_mut(source: &mut [u8; N]) -> Result<Self, Error> {
+		let mut key = Box::<[u8; N]>::try_new_uninit()?;
+
+		unsafe {
+			key.try_borrow_mut()?.as_mut_ptr().copy_from_nonoverlapping(source, 1);
+			zero(source, 1);
+		}
+
+		Ok(unsafe { key.assume_init() })
+	}
+
+	/// Generate random key from the operating system’s random number generator
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_random() -> Result<Self, Error> {
+		let mut key = Box::<[u8; N]>::try_new_uninit()?;
+
+		{
+			let mut bytes = key.try_borrow_mut()?;
+			let bytes = unsafe { std::slice::from_raw_parts_mut(bytes.as_mut_ptr().cast::<MaybeUninit<u8>>(), N) };
+			getrandom::fill_uninit(bytes).map_err(|_| Error::Random)?;
+		}
+
+		Ok(unsafe { key.assume_init() })
+	}
+
+	/// Generate random key from the operating system’s random number generator
+	///
+	/// # Panics
+	///
+	/// Panics if the key cannot be allocated or no random bytes are available.
+	pub fn random() -> Self {
+		Self::try_random().unwrap()
+	}
+
+	/// Decode key from hexadecimal
+	///
+	/// Fails with [`Error::Encoding`] unless `source` consists of exactly `2 * N` hexadecimal digits.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_hex(source: &str) -> Result<Self, Error> {
+		let source = source.as_bytes();
+
+		if source.len() != 2 * N {
+			return Err(Error::Encoding);
+		}
+
+		let mut key = unsafe { Box::<[u8; N]>::try_new_zeroed()?.assume_init() };
+		let mut valid = -1;
+
+		{
+			let mut bytes = key.try_borrow_mut()?;
+
+			for (byte, pair) in bytes.iter_mut().zip(source.chunks_exact(2)) {
+				let (high, high_valid) = decode_hex(pair[0]);
+				let (low, low_valid) = decode_hex(pair[1]);
+
+				*byte = high << 4 | low;
+				valid &= high_valid & low_valid;
+			}
+		}
+
+		// Partially decoded key is wiped on drop
+		if black_box(valid) == 0 { Err(Error::Encoding) } else { Ok(key) }
+	}
+
+	/// Decode key from padded base64 with the standard alphabet
+	///
+	/// Fails with [`Error::Encoding`] unless `source` is the canonical encoding of exactly `N` bytes.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_base64(source: &str) -> Result<Self, Error> {
+		let source = source.as_bytes();
+
+		if source.len() != base64_len(N) {
+			return Err(Error::Encoding);
+		}
+
+		let mut key = unsafe { Box::<[u8; N]>::try_new_zeroed()?.assume_init() };
+		let mut valid = -1;
+
+		{
+			let mut bytes = key.try_borrow_mut()?;
+
+			for (chunk, quad) in bytes.chunks_mut(3).zip(source.chunks_exact(4)) {
+				let mut block = 0u32;
+
+				// Characters beyond the chunk must be padding
+				for (index, &ch) in quad.iter().enumerate() {
+					let (value, mask) = if index <= chunk.len() { decode_base64(ch) } else { (0, eq(i16::from(ch), 0x3d)) };
+					block = block << 6 | u32::from(value);
+					valid &= mask;
+				}
+
+				for (index, byte) in chunk.iter_mut().enumerate() {
+					*byte = (block >> (16 - 8 * index)) as u8;
+				}
+
+				// Unused bits must be zero
+				let unused = (block & (0xffffff >> (8 * chunk.len()))) as i32;
+				valid &= ((unused - 1) >> 31) as i16;
+			}
+		}
+
+		if black_box(valid) == 0 { Err(Error::Encoding) } else { Ok(key) }
+	}
+
+	/// Encode `len` characters into a guarded string
+	#[cfg(feature = "string")]
+	fn try_encode<F>(&self, len: usize, encode: F) -> Result<String, Error>
+		where F: FnOnce(&[u8; N], &mut [MaybeUninit<u8>]) {
+		let mut vec = Vec::<u8>::try_with_capacity(len)?;
+
+		{
+			let key = self.try_borrow()?;
+			let mut string = vec.try_borrow_mut()?;
+
+			encode(&key, &mut string.spare_capacity_mut()[..len]);
+			unsafe { string.set_len(len); }
+		}
+
+		Ok(unsafe { String::from_utf8_unchecked(vec) })
+	}
+
+	/// Encode key as lower‐case hexadecimal into a guarded string
+	#[cfg(feature = "string")]
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_to_hex(&self) -> Result<String, Error> {
+		self.try_encode(2 * N, |key, string| {
+			for (byte, pair) in key.iter().zip(string.chunks_exact_mut(2)) {
+				pair[0].write(encode_hex(byte >> 4));
+				pair[1].write(encode_hex(byte & 0xf));
+			}
+		})
+	}
+
+	/// Encode key as padded base64 with the standard alphabet into a guarded string
+	#[cfg(feature = "string")]
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_to_base64(&self) -> Result<String, Error> {
+		self.try_encode(base64_len(N), |key, string| {
+			for (chunk, quad) in key.chunks(3).zip(string.chunks_exact_mut(4)) {
+				let block = chunk.iter().enumerate().fold(0u32, |block, (index, &byte)| {
+					block | u32::from(byte) << (16 - 8 * index)
+				});
+
+				for (index, ch) in quad.iter_mut().enumerate() {
+					ch.write(if index <= chunk.len() { encode_base64((block >> (18 - 6 * index)) as u8 & 0x3f) } else { b'=' });
+				}
+			}
+		})
+	}
+
+	/// Compare key with `other` in constant time
+	///
+	/// Borrowing the key consumes one access of its [budget](crate::guard::Guard::set_budget).
+	#[allow(clippy::missing_errors_doc)]
+	pub fn ct_eq(&self, other: &[u8; N]) -> Result<bool, Error> {
+		let key = self.try_borrow()?;
+		Ok(black_box(key.iter().zip(other.iter()).fold(0, |diff, (a, b)| diff | (a ^ b))) == 0)
+	}
+
+	/// Compare keys in constant time
+	///
+	/// Borrowing the keys consumes one access of each [budget](crate::guard::Guard::set_budget).
+	#[allow(clippy::missing_errors_doc)]
+	pub fn ct_eq_key(&self, other: &Self) -> Result<bool, Error> {
+		let other = other.try_borrow()?;
+		self.ct_eq(&other)
+	}
+}
+
+impl<const N: usize> PartialEq for Box<[u8; N]> {
+	/// Compare keys in constant time, see [`ct_eq_key`](Self::ct_eq_key)
+	///
+	/// Keys that cannot be borrowed, such as wiped, exhausted or poisoned ones, compare unequal.
+	fn eq(&self, other: &Self) -> bool {
+		self.ct_eq_key(other).unwrap_or(false)
+	}
+}
+
+impl<const N: usize> Eq for Box<[u8; N]> {}
+
+impl<const N: usize> PartialEq<[u8; N]> for Box<[u8; N]> {
+	/// Compare key in constant time, see [`ct_eq`](Self::ct_eq)
+	///
+	/// A key that cannot be borrowed, such as a wiped, exhausted or poisoned one, compares unequal.
+	fn eq(&self, other: &[u8; N]) -> bool {
+		self.ct_eq(other).unwrap_or(false)
+	}
+}
+
+impl<const N: usize> From<&mut [u8; N]> for Box<[u8; N]> {
+	#[inline]
+	fn from(source: &mut [u8; N]) -> Self {
+		Self::try_from_mut(source).unwrap()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn eq() {
+		let mut source = [0x55; 32];
+		let key = Key::from(&mut source);
+		assert_eq!(source, [0; 32]);
+
+		assert!(key == Key::<32>::new([0x55; 32]));
+		assert!(key != Key::<32>::new([0xaa; 32]));
+		assert!(key == [0x55; 32]);
+		assert!(key == key);
+
+		// Comparing consumes budget and never panics
+		let mut key = Key::<32>::new([0x55; 32]);
+		key.set_budget(Some(2)).unwrap();
+		assert!(key.ct_eq(&[0x55; 32]).unwrap());
+		assert!(key == [0x55; 32]);
+		assert!(key.is_wiped());
+		assert!(matches!(key.ct_eq(&[0x55; 32]), Err(Error::Wiped)));
+		assert!(key != [0x55; 32]);
+	}
+
+	#[test]
+	fn random() {
+		let key = Key::<32>::random();
+		assert!(key != Key::<32>::random());
+		assert!(key != [0; 32]);
+	}
+
+	#[test]
+	#[cfg(feature = "string")]
+	fn hex() {
+		let key = Key::<4>::try_from_hex("deadBEEF").unwrap();
+		assert!(key == [0xde, 0xad, 0xbe, 0xef]);
+		assert_eq!(key.try_to_hex().unwrap().borrow().as_str(), "deadbeef");
+
+		let key = Key::<32>::random();
+		assert!(Key::<32>::try_from_hex(key.try_to_hex().unwrap().borrow().as_str()).unwrap() == key);
+
+		assert!(matches!(Key::<4>::try_from_hex("deadbee"), Err(Error::Encoding)));
+		assert!(matches!(Key::<4>::try_from_hex("deadbeeg"), Err(Error::Encoding)));
+		assert!(matches!(Key::<1>::try_from_hex("0:"), Err(Error::Encoding)));
+	}
+
+	#[test]
+	#[cfg(feature = "string")]
+	fn base64() {
+		let cases: [(&[u8], &str); 4] = [(b"", ""), (b"f", "Zg=="), (b"fo", "Zm8="), (b"foobar", "Zm9vYmFy")];
+
+		for (bytes, encoded) in cases {
+			match bytes.len() {
+				0 => assert_eq!(Key::<0>::try_from_base64(encoded).unwrap().try_to_base64().unwrap().len(), 0),
+				1 => assert!(Key::<1>::try_from_base64(encoded).unwrap() == [bytes[0]]),
+				2 => assert!(Key::<2>::try_from_base64(encoded).unwrap() == [bytes[0], bytes[1]]),
+				_ => assert_eq!(Key::<6>::new(*b"foobar").try_to_base64().unwrap().borrow().as_str(), encoded),
+			}
+		}
+
+		let key = Key::<32>::random();
+		assert!(Key::<32>::try_from_base64(key.try_to_base64().unwrap().borrow().as_str()).unwrap() == key);
+		assert_eq!(Key::<2>::try_from_hex("fbff").unwrap().try_to_base64().unwrap().borrow().as_str(), "+/8=");
+
+		assert!(matches!(Key::<1>::try_from_base64("Zg="), Err(Error::Encoding)));
+		assert!(matches!(Key::<1>::try_from_base64("Zh=="), Err(Error::Encoding)));
+		assert!(matches!(Key::<1>::try_from_base64("Zg=a"), Err(Error::Encoding)));
+		assert!(matches!(Key::<2>::try_from_base64("Zm-="), Err(Error::Encoding)));
+	}
+}
@@ -0,0 +1,271 @@
+//! Lazily initialised secrets
+//!
+//! [`OnceSecret`] and [`LazySecret`] hold process‐wide secrets, such as a master key, in a guarded [`Box`]. They may
+//! be placed in statics, are initialised on first use and are wiped at process exit. A secret that is still borrowed
+//! at that point is wiped only once its last reference is dropped, which may not happen before the process ends, and
+//! a [read‐only](OnceSecret::read_only) secret whose allocation is sealed is never wiped.
+//!
+//! Values returned by initialisers pass through the stack before being moved into guarded memory.
+//! [`OnceSecret::get_or_init_with`] initialises a secret in place instead.
+
+use crate::boxed::{Box, InnerBox};
+use crate::error::{BorrowError, Error};
+use crate::guard::Ref;
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, Weak};
+
+/// Secret initialised at most once
+#[must_use]
+pub struct OnceSecret<T> {
+	guard: OnceLock<Arc<Box<T>>>,
+	read_only: bool,
+}
+
+/// Secret initialised on first access
+#[must_use]
+pub struct LazySecret<T, F = fn() -> T> {
+	secret: OnceSecret<T>,
+	init: Mutex<Option<F>>,
+}
+
+/// Type‐erased wiping of registered secrets
+trait Wipe: Send + Sync {
+	fn wipe(&self);
+}
+
+/// Secrets to be wiped at process exit
+static REGISTRY: Mutex<Vec<Weak<dyn Wipe>>> = Mutex::new(Vec::new());
+
+impl<T: Send + Sync> Wipe for Box<T> {
+	fn wipe(&self) {
+		self.exhaust();
+	}
+}
+
+/// Wipe registered secrets that are still alive
+#[ctor::dtor]
+fn wipe() {
+	for secret in REGISTRY.lock().unwrap_or_else(PoisonError::into_inner).drain(..) {
+		if let Some(secret) = secret.upgrade() {
+			secret.wipe();
+		}
+	}
+}
+
+impl<T> OnceSecret<T> {
+	pub const fn new() -> Self {
+		Self { guard: OnceLock::new(), read_only: false }
+	}
+
+	/// Create secret that is [frozen](crate::guard::Guard::freeze) once initialised
+	///
+	/// Where the allocation is sealed, it cannot be wiped at process exit.
+	pub const fn read_only() -> Self {
+		Self { guard: OnceLock::new(), read_only: true }
+	}
+
+	#[must_use] #[inline]
+	pub fn get(&self) -> Option<&Box<T>> {
+		self.guard.get().map(|guard| &**guard)
+	}
+
+	#[must_use] #[inline]
+	pub fn is_initialized(&self) -> bool {
+		self.guard.get().is_some()
+	}
+}
+
+impl<T: Send + Sync + 'static> OnceSecret<T> {
+	/// Register `guard` for wiping at process exit
+	fn guard(&self, mut guard: Box<T>) -> Result<Arc<Box<T>>, Error> {
+		if self.read_only {
+			guard.freeze()?;
+		}
+
+		let guard = Arc::new(guard);
+		let weak: Weak<dyn Wipe> = Arc::downgrade(&guard) as Weak<Box<T>>;
+
+		let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+		registry.retain(|secret| secret.strong_count() > 0);
+		registry.push(weak);
+
+		Ok(guard)
+	}
+
+	/// Get guard, initialising it with the result of `init` if uninitialised
+	///
+	/// The value returned by `init` passes through the stack and is cleared out only after being moved into guarded
+	/// memory. Use [`get_or_try_init_with`](Self::get_or_try_init_with) to initialise it in place instead.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn get_or_try_init<E, F>(&self, init: F) -> Result<&Box<T>, E>
+		where E: From<Error>, F: FnOnce() -> Result<T, E> {
+		self.guard.get_or_try_init(|| Ok(self.guard(Box::try_new(init()?)?)?)).map(|guard| &**guard)
+	}
+
+	/// Get guard, initialising it directly in guarded memory with `init` if uninitialised
+	///
+	/// # Safety
+	///
+	/// `init` must initialise the value.
+	#[allow(clippy::missing_errors_doc)]
+	pub unsafe fn get_or_try_init_with<F>(&self, init: F) -> Result<&Box<T>, Error>
+		where F: FnOnce(&mut MaybeUninit<T>) {
+		self.guard.get_or_try_init(|| self.guard(Box::try_new_with(init)?)).map(|guard| &**guard)
+	}
+
+	/// Get guard, initialising it directly in guarded memory with `init` if uninitialised
+	///
+	/// # Safety
+	///
+	/// `init` must initialise the value.
+	///
+	/// # Panics
+	///
+	/// Panics if the guarded memory cannot be allocated or protected.
+	pub unsafe fn get_or_init_with<F>(&self, init: F) -> &Box<T>
+		where F: FnOnce(&mut MaybeUninit<T>) {
+		self.get_or_try_init_with(init).unwrap()
+	}
+
+	/// Get guard, initialising it with the result of `init` if uninitialised
+	///
+	/// # Panics
+	///
+	/// Panics if the guarded memory cannot be allocated or protected.
+	pub fn get_or_init<F>(&self, init: F) -> &Box<T>
+		where F: FnOnce() -> T {
+		self.get_or_try_init::<Error, _>(|| Ok(init())).unwrap()
+	}
+
+	/// Initialise with `value`, returning it if already initialised
+	#[allow(clippy::missing_errors_doc)]
+	pub fn set(&self, value: T) -> Result<(), T> {
+		let mut value = Some(value);
+		self.get_or_init(|| value.take().unwrap());
+
+		match value {
+			None => Ok(()),
+			Some(value) => Err(value),
+		}
+	}
+}
+
+impl<T> Default for OnceSecret<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> fmt::Debug for OnceSecret<T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.get() {
+			Some(guard) => fmt.debug_tuple("OnceSecret").field(guard).finish(),
+			None => write!(fmt, "OnceSecret(<uninit>)"),
+		}
+	}
+}
+
+impl<T, F> LazySecret<T, F> {
+	pub const fn new(init: F) -> Self {
+		Self { secret: OnceSecret::new(), init: Mutex::new(Some(init)) }
+	}
+
+	/// Create secret that is [frozen](crate::guard::Guard::freeze) once initialised, see [`OnceSecret::read_only`]
+	pub const fn read_only(init: F) -> Self {
+		Self { secret: OnceSecret::read_only(), init: Mutex::new(Some(init)) }
+	}
+}
+
+impl<T: Send + Sync + 'static, F: FnOnce() -> T> LazySecret<T, F> {
+	/// Get guard, initialising it on first access
+	///
+	/// # Panics
+	///
+	/// Panics if the guarded memory cannot be allocated or protected, or if initialisation has panicked before.
+	pub fn force(&self) -> &Box<T> {
+		self.secret.get_or_init(|| {
+			let init = self.init.lock().unwrap_or_else(PoisonError::into_inner).take();
+			init.expect("initialisation panicked")()
+		})
+	}
+
+	/// Immutably borrow secret, initialising it on first access
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow(&self) -> Result<Ref<'_, InnerBox<T>>, BorrowError<Ref<'_, InnerBox<T>>>> {
+		self.force().try_borrow()
+	}
+
+	/// Immutably borrow secret, initialising it on first access
+	///
+	/// # Panics
+	///
+	/// Panics if the secret cannot be initialised or borrowed.
+	#[inline] #[track_caller]
+	pub fn borrow(&self) -> Ref<'_, InnerBox<T>> {
+		self.force().borrow()
+	}
+}
+
+impl<T, F> fmt::Debug for LazySecret<T, F> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_tuple("LazySecret").field(&self.secret).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn once() {
+		static SECRET: OnceSecret<[u8; 32]> = OnceSecret::new();
+
+		assert!(SECRET.get().is_none());
+		assert_eq!(SECRET.set([0x55; 32]), Ok(()));
+		assert_eq!(SECRET.set([0xaa; 32]), Err([0xaa; 32]));
+		assert_eq!(*SECRET.get_or_init(|| [0xff; 32]).borrow(), [0x55; 32]);
+
+		let failed = OnceSecret::<u32>::new();
+		assert!(matches!(failed.get_or_try_init(|| Err(Error::Alloc)), Err(Error::Alloc)));
+		assert!(!failed.is_initialized());
+
+		let secret = OnceSecret::<[u8; 32]>::read_only();
+		let guard = unsafe { secret.get_or_init_with(|value| { value.write([0x55; 32]); }) };
+		assert!(guard.is_frozen());
+		assert_eq!(*guard.borrow(), [0x55; 32]);
+		assert_eq!(*unsafe { secret.get_or_init_with(|value| { value.write([0xff; 32]); }) }.borrow(), [0x55; 32]);
+	}
+
+	#[test]
+	fn lazy() {
+		static SECRET: LazySecret<u32> = LazySecret::read_only(|| 0xdeadbeef);
+
+		assert!(!SECRET.secret.is_initialized());
+		assert_eq!(*SECRET.borrow(), 0xdeadbeef);
+		assert!(SECRET.force().is_frozen());
+		assert!(format!("{SECRET:?}").starts_with("LazySecret(OnceSecret(Guard("));
+	}
+
+	#[test]
+	fn wipe() {
+		let secret = OnceSecret::<u32>::new();
+		let guard = Arc::downgrade(secret.guard.get_or_init(|| secret.guard(Box::new(0x55555555)).unwrap()));
+
+		guard.upgrade().unwrap().wipe();
+		assert!(secret.get().unwrap().is_wiped());
+
+		drop(secret);
+		assert!(guard.upgrade().is_none());
+
+		let secret = OnceSecret::<u32>::read_only();
+		let guard = secret.guard.get_or_init(|| secret.guard(Box::new(0x55555555)).unwrap());
+
+		guard.wipe();
+		// Sealed secrets cannot be wiped
+		assert!(secret.get().unwrap().is_frozen());
+		assert_eq!(secret.get().unwrap().is_wiped(), !secret.get().unwrap().is_sealed());
+	}
+}
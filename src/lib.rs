@@ -1,21 +1,37 @@
 #![allow(internal_features)]
+#![cfg_attr(test, feature(test))]
 #![feature(
 	allocator_api,
+	clone_to_uninit,
+	coerce_unsized,
 	core_intrinsics,
+	layout_for_ptr,
 	maybe_uninit_slice,
+	once_cell_try,
 	ptr_as_ref_unchecked,
 	slice_ptr_get,
 	sync_unsafe_cell,
 )]
 
 pub mod auxiliary;
+pub mod error;
 pub mod pages;
 pub mod alloc;
+pub mod arena;
+pub mod audit;
+pub mod stats;
+pub mod redact;
+pub mod guard;
 pub mod boxed;
+pub mod key;
+pub mod expiry;
 pub mod vec;
+pub mod sync;
+pub mod multi;
+pub mod lazy;
 
 #[cfg(feature = "string")]
 pub mod string;
 
 mod traits;
-mod guard;
+mod wait;
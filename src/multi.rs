@@ -0,0 +1,265 @@
+//! Joint borrowing of multiple guards
+//!
+//! [`borrow_all`] borrows a tuple or array of guards at once. Either all members are borrowed or, if borrowing any
+//! member fails, those already borrowed are released again and the error is returned.
+//!
+//! Members are acquired in order of their address, regardless of their position in the tuple or array, so that
+//! jointly borrowing overlapping sets of [`RwGuard`]s from several threads cannot deadlock.
+
+use crate::error::Error;
+use crate::guard::{Guard, Ref, RefMut};
+use crate::sync::RwGuard;
+use crate::traits::Protectable;
+
+/// Immutable borrow of a shared [`RwGuard`]
+#[must_use]
+#[derive(Debug)]
+pub struct Read<'t, T: Protectable>(pub &'t RwGuard<T>);
+
+/// Mutable borrow of a shared [`RwGuard`]
+#[must_use]
+#[derive(Debug)]
+pub struct Write<'t, T: Protectable>(pub &'t RwGuard<T>);
+
+/// Member of a joint borrow
+pub trait Member {
+	type Ref;
+
+	/// Address determining the order of acquisition
+	fn address(&self) -> usize;
+
+	/// Whether the borrow excludes any other borrow of the same guard
+	fn exclusive(&self) -> bool;
+
+	#[allow(clippy::missing_errors_doc)]
+	fn acquire(self) -> Result<Self::Ref, Error>;
+}
+
+/// Tuple or array of members
+pub trait Members {
+	type Refs;
+
+	#[allow(clippy::missing_errors_doc)]
+	fn acquire(self) -> Result<Self::Refs, Error>;
+}
+
+/// Borrow all `members`, returning a tuple or array of references
+///
+/// Fails with [`Error::Borrowed`] if the same guard is borrowed more than once and any of these borrows is
+/// [exclusive](Member::exclusive).
+#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+pub fn borrow_all<M: Members>(members: M) -> Result<M::Refs, Error> {
+	members.acquire()
+}
+
+/// Order of acquisition of members with the given addresses and exclusivity
+fn order<const N: usize>(keys: [(usize, bool); N]) -> Result<[usize; N], Error> {
+	let mut order: [usize; N] = std::array::from_fn(|index| index);
+	order.sort_unstable_by_key(|&index| keys[index].0);
+
+	if order.windows(2).any(|pair| keys[pair[0]].0 == keys[pair[1]].0 && (keys[pair[0]].1 || keys[pair[1]].1)) {
+		return Err(Error::Borrowed);
+	}
+
+	Ok(order)
+}
+
+impl<'t, T: Protectable> Member for &'t Guard<T> {
+	type Ref = Ref<'t, T>;
+
+	#[inline]
+	fn address(&self) -> usize {
+		std::ptr::from_ref(*self).addr()
+	}
+
+	#[inline]
+	fn exclusive(&self) -> bool {
+		false
+	}
+
+	#[inline] #[track_caller]
+	fn acquire(self) -> Result<Self::Ref, Error> {
+		Ok(self.try_borrow()?)
+	}
+}
+
+impl<'t, T: Protectable> Member for &'t mut Guard<T> {
+	type Ref = RefMut<'t, T>;
+
+	#[inline]
+	fn address(&self) -> usize {
+		std::ptr::from_ref(&**self).addr()
+	}
+
+	#[inline]
+	fn exclusive(&self) -> bool {
+		true
+	}
+
+	#[inline] #[track_caller]
+	fn acquire(self) -> Result<Self::Ref, Error> {
+		Ok(self.try_borrow_mut()?)
+	}
+}
+
+impl<'t, T: Protectable> Member for Read<'t, T> {
+	type Ref = Ref<'t, T>;
+
+	#[inline]
+	fn address(&self) -> usize {
+		std::ptr::from_ref(self.0).addr()
+	}
+
+	#[inline]
+	fn exclusive(&self) -> bool {
+		false
+	}
+
+	#[inline] #[track_caller]
+	fn acquire(self) -> Result<Self::Ref, Error> {
+		Ok(self.0.read()?)
+	}
+}
+
+impl<'t, T: Protectable> Member for Write<'t, T> {
+	type Ref = RefMut<'t, T>;
+
+	#[inline]
+	fn address(&self) -> usize {
+		std::ptr::from_ref(self.0).addr()
+	}
+
+	#[inline]
+	fn exclusive(&self) -> bool {
+		true
+	}
+
+	#[inline] #[track_caller]
+	fn acquire(self) -> Result<Self::Ref, Error> {
+		Ok(self.0.write()?)
+	}
+}
+
+impl<M: Member, const N: usize> Members for [M; N] {
+	type Refs = [M::Ref; N];
+
+	#[track_caller]
+	fn acquire(self) -> Result<Self::Refs, Error> {
+		let order = order::<N>(std::array::from_fn(|index| (self[index].address(), self[index].exclusive())))?;
+		let mut members = self.map(Some);
+		let mut refs: [Option<M::Ref>; N] = std::array::from_fn(|_| None);
+
+		// References acquired so far are released on error
+		for index in order {
+			refs[index] = Some(members[index].take().unwrap().acquire()?);
+		}
+
+		Ok(refs.map(Option::unwrap))
+	}
+}
+
+macro_rules! members {
+	($count:literal: $($member:ident $index:tt),+) => {
+		impl<$($member: Member),+> Members for ($($member,)+) {
+			type Refs = ($($member::Ref,)+);
+
+			#[track_caller]
+			fn acquire(self) -> Result<Self::Refs, Error> {
+				let order = order::<$count>([$((self.$index.address(), self.$index.exclusive())),+])?;
+				let mut members = ($(Some(self.$index),)+);
+				let mut refs = ($(None::<$member::Ref>,)+);
+
+				// References acquired so far are released on error
+				for index in order {
+					match index {
+						$($index => refs.$index = Some(members.$index.take().unwrap().acquire()?),)+
+						_ => unreachable!(),
+					}
+				}
+
+				Ok(($(refs.$index.unwrap(),)+))
+			}
+		}
+	};
+}
+
+members!(1: A 0);
+members!(2: A 0, B 1);
+members!(3: A 0, B 1, C 2);
+members!(4: A 0, B 1, C 2, D 3);
+members!(5: A 0, B 1, C 2, D 3, E 4);
+members!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::boxed::Box;
+	use crate::vec::Vec;
+
+	#[test]
+	fn tuple() {
+		let mut key = Box::<[u8; 32]>::new([0x55; 32]);
+		let nonce = Vec::<u8>::from(vec![0xaa; 12]);
+
+		{
+			let (mut key, nonce) = borrow_all((&mut key, &nonce)).unwrap();
+			key[0] = nonce[0];
+		}
+
+		assert!(key.with(|key| key[0] == 0xaa));
+	}
+
+	#[test]
+	fn rollback() {
+		let first = Box::<u32>::new(0x55555555);
+		let mut second = Box::<u32>::new(0xdeadbeef);
+		second.set_budget(Some(0)).unwrap();
+
+		assert!(matches!(borrow_all([&first, &second]), Err(Error::Wiped)));
+		assert!(matches!(borrow_all((&second, &first)), Err(Error::Wiped)));
+		assert!(format!("{first:?}").starts_with("Guard(0,"));
+
+		let shared = RwGuard::new(Box::<u32>::new(0));
+		assert!(matches!(borrow_all((Write(&shared), Read(&shared))), Err(Error::Borrowed)));
+		assert_eq!(borrow_all([Read(&shared), Read(&shared)]).unwrap().map(|value| *value), [0, 0]);
+	}
+
+	#[test]
+	fn concurrent() {
+		use std::sync::{Arc, Barrier};
+		use std::thread;
+
+		const LIMIT: u32 = 1024;
+
+		let first = Arc::new(RwGuard::new(Box::<u32>::new(0)));
+		let second = Arc::new(RwGuard::new(Box::<u32>::new(0)));
+		let barrier = Arc::new(Barrier::new(2));
+
+		let threads: std::vec::Vec<_> = [false, true].into_iter().map(|reverse| {
+			let (first, second, start) = (first.clone(), second.clone(), barrier.clone());
+
+			thread::spawn(move || {
+				start.wait();
+
+				for _ in 0..LIMIT {
+					let (mut a, mut b) = if reverse {
+						let (b, a) = borrow_all((Write(&second), Write(&first))).unwrap();
+						(a, b)
+					} else {
+						borrow_all((Write(&first), Write(&second))).unwrap()
+					};
+
+					assert_eq!(*a, *b);
+					*a += 1;
+					*b += 1;
+				}
+			})
+		}).collect();
+
+		for thread in threads {
+			thread.join().unwrap();
+		}
+
+		assert_eq!(*first.read().unwrap(), 2 * LIMIT);
+	}
+}
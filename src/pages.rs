@@ -5,7 +5,7 @@ use crate::traits::{AsPages, Protectable};
 use std::cell::SyncUnsafeCell;
 use std::convert::TryInto;
 use std::intrinsics::likely;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use std::marker::PhantomData;
 use std::mem::{MaybeUninit, ManuallyDrop};
 use std::ops::Range;
@@ -239,6 +239,26 @@ impl<'t> Pages<'t> {
 		}
 	}
 
+	/// Seal pages, preventing changes of protection and unmapping
+	///
+	/// Requires Linux 6.10 or later. Fails with [`ErrorKind::Unsupported`](std::io::ErrorKind::Unsupported) on other
+	/// platforms.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn seal(&self) -> Result<(), Error> {
+		#[cfg(target_os = "linux")] {
+			use libc::{syscall, SYS_mseal};
+
+			match unsafe { syscall(SYS_mseal, self.as_ptr::<c_void>(), self.0.len(), 0) } {
+				0 => Ok(()),
+				_ => Err(Error::last_os_error()),
+			}
+		}
+
+		#[cfg(not(target_os = "linux"))] {
+			Err(std::io::ErrorKind::Unsupported.into())
+		}
+	}
+
 	#[must_use]
 	pub fn pages(&'t self, range: Range<usize>) -> Option<Pages<'t>> {
 		if likely(range.start < self.len() && range.end <= self.len()) {
@@ -508,6 +528,13 @@ impl<const N: usize> GuardedAlloc<N> {
 		unsafe { Pages::from_slice(self.into_slice()) }
 	}
 
+	/// Convert into pages including guard pages
+	#[allow(clippy::missing_panics_doc)]
+	pub fn into_outer_pages(self) -> Pages<'static> {
+		let len = self.0.len();
+		unsafe { Pages::from_slice(ManuallyDrop::new(self.0).pages(0 .. len).unwrap().into_slice()) }
+	}
+
 	#[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 	pub fn shrink(self, size: usize) -> Result<Self, Error> {
 		let outer = Self::outer_size(size);
@@ -546,6 +573,27 @@ impl<T: AsPages> Protectable for T {
 
 		Ok(())
 	}
+
+	fn seal(&mut self) -> Result<bool, Error> {
+		let (Some(pages), Some(outer)) = (self.as_pages(), self.as_outer_pages()) else {
+			return Ok(false);
+		};
+
+		pages.protect(Protection::ReadOnly)?;
+
+		match outer.seal() {
+			Ok(()) => Ok(true),
+			Err(err) => {
+				pages.protect(Protection::NoAccess)?;
+
+				// Kernels without support fail with ENOSYS
+				match err.kind() {
+					ErrorKind::Unsupported => Ok(false),
+					_ => Err(err),
+				}
+			}
+		}
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,154 @@
+//! Redacted formatting
+//!
+//! The [`Debug`](fmt::Debug) implementations of guards and references never show the guarded contents. Where part
+//! of a secret must be shown, e.g. for support tooling, [`Masked`] reveals a configurable prefix and suffix and
+//! replaces everything else with a fill character.
+//!
+//! Bytes are formatted as lower‐case hexadecimal, strings by extended grapheme cluster. If the prefix and suffix
+//! together would cover the entire contents, nothing is revealed.
+
+use std::fmt;
+
+/// Masking policy
+#[must_use]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mask {
+	prefix: usize,
+	suffix: usize,
+	fill: char,
+}
+
+/// Contents that can be formatted masked
+///
+/// Byte arrays and slices are formatted as hexadecimal, strings by extended grapheme cluster.
+pub trait Maskable {
+	/// Write contents, revealing only what `mask` permits
+	#[allow(clippy::missing_errors_doc)]
+	fn write_masked(&self, fmt: &mut fmt::Formatter<'_>, mask: &Mask) -> fmt::Result;
+}
+
+/// Masked formatter of guarded contents
+///
+/// The guard is immutably borrowed only while formatting. If it cannot be borrowed, `<redacted>` is shown instead.
+#[must_use]
+pub struct Masked<'t, G: ?Sized> {
+	pub(crate) guard: &'t G,
+	pub(crate) mask: Mask,
+}
+
+impl Mask {
+	/// Mask revealing nothing
+	pub const fn new() -> Self {
+		Self { prefix: 0, suffix: 0, fill: '*' }
+	}
+
+	/// Reveal first `prefix` units
+	pub const fn prefix(self, prefix: usize) -> Self {
+		Self { prefix, ..self }
+	}
+
+	/// Reveal last `suffix` units
+	pub const fn suffix(self, suffix: usize) -> Self {
+		Self { suffix, ..self }
+	}
+
+	/// Replace hidden characters with `fill`
+	pub const fn fill(self, fill: char) -> Self {
+		Self { fill, ..self }
+	}
+
+	/// Write `units`, each `width` characters wide, of which `unit` writes those revealed
+	pub(crate) fn write<I, F>(&self, fmt: &mut fmt::Formatter<'_>, units: I, width: usize, mut unit: F) -> fmt::Result
+		where I: Iterator + Clone, F: FnMut(&mut fmt::Formatter<'_>, I::Item) -> fmt::Result {
+		let len = units.clone().count();
+		let (prefix, suffix) = match self.prefix.checked_add(self.suffix) {
+			Some(shown) if shown < len => (self.prefix, self.suffix),
+			_ => (0, 0),
+		};
+
+		for (index, item) in units.enumerate() {
+			if index < prefix || index >= len - suffix {
+				unit(fmt, item)?;
+			} else {
+				for _ in 0..width {
+					fmt::Write::write_char(fmt, self.fill)?;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Write bytes as hexadecimal
+	pub(crate) fn write_bytes(&self, fmt: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+		self.write(fmt, bytes.iter(), 2, |fmt, byte| write!(fmt, "{byte:02x}"))
+	}
+
+	/// Write string by extended grapheme cluster
+	#[cfg(feature = "string")]
+	pub(crate) fn write_str(&self, fmt: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+		use unicode_segmentation::UnicodeSegmentation;
+
+		self.write(fmt, string.graphemes(true), 1, |fmt, grapheme| fmt.write_str(grapheme))
+	}
+}
+
+impl Maskable for [u8] {
+	#[inline]
+	fn write_masked(&self, fmt: &mut fmt::Formatter<'_>, mask: &Mask) -> fmt::Result {
+		mask.write_bytes(fmt, self)
+	}
+}
+
+impl<const N: usize> Maskable for [u8; N] {
+	#[inline]
+	fn write_masked(&self, fmt: &mut fmt::Formatter<'_>, mask: &Mask) -> fmt::Result {
+		mask.write_bytes(fmt, self)
+	}
+}
+
+#[cfg(feature = "string")]
+impl Maskable for str {
+	#[inline]
+	fn write_masked(&self, fmt: &mut fmt::Formatter<'_>, mask: &Mask) -> fmt::Result {
+		mask.write_str(fmt, self)
+	}
+}
+
+impl Default for Mask {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<G: ?Sized> fmt::Debug for Masked<'_, G> where Self: fmt::Display {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(fmt, "\"{self}\"")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	struct Bytes<'t>(&'t [u8], Mask);
+
+	impl fmt::Display for Bytes<'_> {
+		fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+			self.1.write_bytes(fmt, self.0)
+		}
+	}
+
+	#[test]
+	fn mask() {
+		let bytes = [0xde, 0xad, 0xbe, 0xef];
+
+		assert_eq!(Bytes(&bytes, Mask::new()).to_string(), "********");
+		assert_eq!(Bytes(&bytes, Mask::new().suffix(2)).to_string(), "****beef");
+		assert_eq!(Bytes(&bytes, Mask::new().prefix(1).suffix(1).fill('.')).to_string(), "de....ef");
+		assert_eq!(Bytes(&bytes, Mask::new().prefix(2).suffix(2)).to_string(), "********");
+		assert_eq!(Bytes(&bytes, Mask::new().prefix(usize::MAX).suffix(1)).to_string(), "********");
+		assert_eq!(Bytes(&[], Mask::new().prefix(1)).to_string(), "");
+	}
+}
@@ -0,0 +1,133 @@
+//! Access statistics
+//!
+//! A [`Guard`](crate::guard::Guard) with [statistics enabled](crate::guard::Guard::set_stats) counts its borrows
+//! and measures for how long its contents are accessible.
+
+use crate::audit::{Kind, Phase};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Snapshot of access statistics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+	/// Number of borrows, including mutable borrows
+	pub borrows: u64,
+
+	/// Number of mutable borrows
+	pub mutable_borrows: u64,
+
+	/// Cumulative time the contents were accessible
+	pub unlocked: Duration,
+
+	/// Longest single interval the contents were accessible
+	pub longest: Duration,
+}
+
+/// Access counters
+#[derive(Debug)]
+pub(crate) struct Counters {
+	origin: Instant,
+	borrows: AtomicU64,
+	mutable_borrows: AtomicU64,
+
+	/// Nanoseconds since origin at which the contents last became accessible
+	since: AtomicU64,
+
+	unlocked: AtomicU64,
+	longest: AtomicU64,
+}
+
+impl Counters {
+	pub(crate) fn new() -> Self {
+		Self {
+			origin: Instant::now(),
+			borrows: AtomicU64::new(0),
+			mutable_borrows: AtomicU64::new(0),
+			since: AtomicU64::new(0),
+			unlocked: AtomicU64::new(0),
+			longest: AtomicU64::new(0),
+		}
+	}
+
+	fn elapsed(&self) -> u64 {
+		u64::try_from(self.origin.elapsed().as_nanos()).unwrap_or(u64::MAX)
+	}
+
+	/// Count borrow
+	pub(crate) fn borrow(&self, kind: Kind) {
+		self.borrows.fetch_add(1, Ordering::Relaxed);
+
+		if kind == Kind::Mutable {
+			self.mutable_borrows.fetch_add(1, Ordering::Relaxed);
+		}
+	}
+
+	/// Measure change of accessibility
+	///
+	/// Changes of accessibility are serialised by the guard state.
+	pub(crate) fn transition(&self, phase: Phase) {
+		let now = self.elapsed();
+
+		match phase {
+			Phase::Acquire => self.since.store(now, Ordering::Relaxed),
+			Phase::Release => {
+				let interval = now.saturating_sub(self.since.load(Ordering::Relaxed));
+				self.unlocked.fetch_add(interval, Ordering::Relaxed);
+				self.longest.fetch_max(interval, Ordering::Relaxed);
+			},
+		}
+	}
+
+	pub(crate) fn stats(&self) -> Stats {
+		Stats {
+			borrows: self.borrows.load(Ordering::Relaxed),
+			mutable_borrows: self.mutable_borrows.load(Ordering::Relaxed),
+			unlocked: Duration::from_nanos(self.unlocked.load(Ordering::Relaxed)),
+			longest: Duration::from_nanos(self.longest.load(Ordering::Relaxed)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::boxed::Box;
+
+	use std::thread;
+	use std::time::Duration;
+
+	#[test]
+	fn stats() {
+		let mut test = Box::<u32>::new(0x55555555);
+		assert_eq!(test.stats(), None);
+
+		test.set_stats(true);
+		assert_eq!(test.stats().unwrap().borrows, 0);
+		assert!(!test.is_accessible());
+
+		{
+			let immutable = test.borrow();
+			let other = test.borrow();
+			assert!(test.is_accessible());
+			assert_eq!(test.readers(), 2);
+
+			thread::sleep(Duration::from_millis(20));
+			drop((immutable, other));
+		}
+
+		assert!(!test.is_accessible());
+		assert_eq!(test.readers(), 0);
+
+		{
+			let _mutable = test.borrow_mut();
+		}
+
+		let stats = test.stats().unwrap();
+		assert_eq!((stats.borrows, stats.mutable_borrows), (3, 1));
+		assert!(stats.longest >= Duration::from_millis(20));
+		assert!(stats.unlocked >= stats.longest);
+
+		test.set_stats(false);
+		assert_eq!(test.stats(), None);
+	}
+}
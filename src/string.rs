@@ -1,11 +1,17 @@
 //! Guarded [string](std::string) type
 
 use crate::auxiliary::zero;
+use crate::alloc::Sensitive;
+use crate::audit::Hook;
+use crate::error::{Error, BorrowError};
 use crate::guard;
+use crate::redact::{Mask, Masked};
+use crate::stats::Stats;
 use crate::vec::{InnerVec, Vec};
 
 use std::cmp::{PartialEq, min, max};
 use std::convert::From;
+use std::fmt;
 use std::iter::FromIterator;
 use std::mem::MaybeUninit;
 use std::str::Chars;
@@ -14,18 +20,19 @@ use unicode_normalization::UnicodeNormalization;
 use unicode_normalization::char::decompose_canonical;
 
 /// Guarded [string](std::string::String) type
+///
+/// Formatting with [`Debug`](fmt::Debug) never shows the contents. Part of the contents may be shown
+/// [masked](Self::masked).
 #[must_use]
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct String(Vec<u8>);
 
 /// Reference to immutably borrowed guarded [`String`]
 #[must_use]
-#[derive(Debug)]
 pub struct Ref<'t>(guard::Ref<'t, InnerVec<u8>>);
 
 /// Reference to mutably borrowed guarded [`String`]
 #[must_use]
-#[derive(Debug)]
 pub struct RefMut<'t>(guard::RefMut<'t, InnerVec<u8>>);
 
 impl String {
@@ -49,11 +56,63 @@ impl String {
 		Self(Vec::new())
 	}
 
+	#[inline]
+	pub fn new_in(alloc: Sensitive) -> Self {
+		Self(Vec::new_in(alloc))
+	}
+
 	#[inline]
 	pub fn with_capacity(capacity: usize) -> Self {
 		Self(Vec::with_capacity(capacity))
 	}
 
+	#[inline]
+	pub fn with_capacity_in(capacity: usize, alloc: Sensitive) -> Self {
+		Self(Vec::with_capacity_in(capacity, alloc))
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_with_capacity(capacity: usize) -> Result<Self, Error> {
+		Self::try_with_capacity_in(capacity, Sensitive)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_with_capacity_in(capacity: usize, alloc: Sensitive) -> Result<Self, Error> {
+		Ok(Self(Vec::try_with_capacity_in(capacity, alloc)?))
+	}
+
+	/// Create guarded string from `source`, returning an error instead of panicking on failure
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_from(source: &str) -> Result<Self, Error> {
+		Self::try_from_in(source, Sensitive)
+	}
+
+	/// Create guarded string from `source` using `alloc`, returning an error instead of panicking on failure
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_from_in(source: &str, alloc: Sensitive) -> Result<Self, Error> {
+		let (lower, upper) = source.nfd().size_hint();
+		let mut string = Self::try_with_capacity_in(upper.unwrap_or(lower), alloc)?;
+
+		string.try_borrow_mut()?.try_push_str(source)?;
+
+		Ok(string)
+	}
+
+	/// Create guarded string from guarded bytes
+	///
+	/// # Safety
+	///
+	/// `vec` must contain valid UTF‐8 in normalisation form D.
+	#[inline]
+	pub(crate) unsafe fn from_utf8_unchecked(vec: Vec<u8>) -> Self {
+		Self(vec)
+	}
+
+	#[inline]
+	pub fn allocator(&self) -> &Sensitive {
+		self.0.allocator()
+	}
+
 	#[inline]
 	pub fn capacity(&self) -> usize {
 		self.0.capacity()
@@ -79,15 +138,154 @@ impl String {
 		self.0.reserve_exact(capacity);
 	}
 
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_reserve(&mut self, capacity: usize) -> Result<(), Error> {
+		self.0.try_reserve(capacity)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_reserve_exact(&mut self, capacity: usize) -> Result<(), Error> {
+		self.0.try_reserve_exact(capacity)
+	}
+
+	#[must_use] #[inline]
+	pub fn is_poisoned(&self) -> bool {
+		self.0.is_poisoned()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_wiped(&self) -> bool {
+		self.0.is_wiped()
+	}
+
 	#[inline]
+	pub fn clear_poison(&self) {
+		self.0.clear_poison();
+	}
+
+	#[inline]
+	pub fn set_wipe_on_poison(&mut self, wipe: bool) {
+		self.0.set_wipe_on_poison(wipe);
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn set_budget(&mut self, budget: Option<usize>) -> Result<(), Error> {
+		self.0.set_budget(budget)
+	}
+
+	#[must_use] #[inline]
+	pub fn budget(&self) -> Option<usize> {
+		self.0.budget()
+	}
+
+	#[inline]
+	pub fn set_audit_hook(&mut self, hook: Option<&'static dyn Hook>) {
+		self.0.set_audit_hook(hook);
+	}
+
+	#[inline]
+	pub fn set_stats(&mut self, enabled: bool) {
+		self.0.set_stats(enabled);
+	}
+
+	#[must_use] #[inline]
+	pub fn stats(&self) -> Option<Stats> {
+		self.0.stats()
+	}
+
+	/// Make string permanently read‐only, see [`Guard::freeze`](crate::guard::Guard::freeze)
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn freeze(&mut self) -> Result<(), Error> {
+		self.0.freeze()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_frozen(&self) -> bool {
+		self.0.is_frozen()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_sealed(&self) -> bool {
+		self.0.is_sealed()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_accessible(&self) -> bool {
+		self.0.is_accessible()
+	}
+
+	#[must_use] #[inline]
+	pub fn readers(&self) -> usize {
+		self.0.readers()
+	}
+
+	#[inline] #[track_caller]
 	pub fn borrow(&self) -> Ref<'_> {
 		Ref(self.0.borrow())
 	}
 
-	#[inline]
+	#[inline] #[track_caller]
 	pub fn borrow_mut(&mut self) -> RefMut<'_> {
 		RefMut(self.0.borrow_mut())
 	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow(&self) -> Result<Ref<'_>, BorrowError<Ref<'_>>> {
+		self.0.try_borrow().map(Ref).map_err(|err| err.map(Ref))
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_borrow_mut(&mut self) -> Result<RefMut<'_>, BorrowError<RefMut<'_>>> {
+		self.0.try_borrow_mut().map(RefMut).map_err(|err| err.map(RefMut))
+	}
+
+	/// Clone into a new guarded allocation, splitting the remaining access budget, see [`Vec::try_clone`]
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_clone(&self) -> Result<Self, Error> {
+		Ok(Self(self.0.try_clone()?))
+	}
+
+	/// Format by extended grapheme cluster, revealing only what `mask` permits
+	#[inline]
+	pub fn masked(&self, mask: Mask) -> Masked<'_, Self> {
+		Masked { guard: self, mask }
+	}
+
+	/// Call `f` with the string, which is accessible only for the duration of the call
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_with<R, F>(&self, f: F) -> Result<R, Error>
+		where F: FnOnce(&str) -> R {
+		Ok(f(self.try_borrow()?.as_str()))
+	}
+
+	/// Call `f` with a mutable reference to the string, which is accessible only for the duration of the call
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_with_mut<R, F>(&mut self, f: F) -> Result<R, Error>
+		where F: FnOnce(&mut RefMut<'_>) -> R {
+		Ok(f(&mut self.try_borrow_mut()?))
+	}
+
+	/// Call `f` with the string, which is accessible only for the duration of the call
+	///
+	/// # Panics
+	///
+	/// Panics if the string cannot be borrowed.
+	#[inline] #[track_caller]
+	pub fn with<R, F>(&self, f: F) -> R
+		where F: FnOnce(&str) -> R {
+		self.try_with(f).unwrap()
+	}
+
+	/// Call `f` with a mutable reference to the string, which is accessible only for the duration of the call
+	///
+	/// # Panics
+	///
+	/// Panics if the string cannot be borrowed.
+	#[inline] #[track_caller]
+	pub fn with_mut<R, F>(&mut self, f: F) -> R
+		where F: FnOnce(&mut RefMut<'_>) -> R {
+		self.try_with_mut(f).unwrap()
+	}
 }
 
 impl FromIterator<char> for String {
@@ -140,6 +338,28 @@ impl From<std::string::String> for String {
 	}
 }
 
+impl Clone for String {
+	#[track_caller]
+	fn clone(&self) -> Self {
+		self.try_clone().unwrap()
+	}
+}
+
+impl fmt::Debug for String {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_tuple("String").field(&self.0).finish()
+	}
+}
+
+impl fmt::Display for Masked<'_, String> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.guard.try_borrow() {
+			Ok(string) => self.mask.write_str(fmt, string.as_str()),
+			Err(_) => fmt.write_str("<redacted>"),
+		}
+	}
+}
+
 impl Ref<'_> {
 	#[must_use] #[inline]
 	pub fn as_bytes(&self) -> &[u8] {
@@ -171,6 +391,12 @@ impl PartialEq<RefMut<'_>> for Ref<'_> {
 	}
 }
 
+impl fmt::Debug for Ref<'_> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_tuple("Ref").field(&self.0).finish()
+	}
+}
+
 impl RefMut<'_> {
 	#[must_use] #[inline]
 	pub fn as_bytes(&self) -> &[u8] {
@@ -199,28 +425,36 @@ impl RefMut<'_> {
 
 	pub fn push(&mut self, ch: char) {
 		decompose_canonical(ch, |decomp| {
-			self.0.0.reserve(decomp.len_utf8());
+			self.0.reserve(decomp.len_utf8());
 			decomp.encode_utf8(unsafe { MaybeUninit::slice_assume_init_mut(self.0.spare_capacity_mut()) });
-			unsafe { self.0.0.set_len(self.0.len() + decomp.len_utf8()); }
+			unsafe { self.0.set_len(self.0.len() + decomp.len_utf8()); }
 		});
 	}
 
+	#[inline]
 	pub fn push_str(&mut self, string: &str) {
+		self.try_push_str(string).unwrap();
+	}
+
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_push_str(&mut self, string: &str) -> Result<(), Error> {
 		let iter = string.nfd();
 		let (lower, upper) = iter.size_hint();
 
-		self.0.0.reserve(upper.unwrap_or(lower));
+		self.0.inner_mut().try_reserve(upper.unwrap_or(lower))?;
 
 		for decomp in iter {
-			self.0.0.reserve(decomp.len_utf8());
+			self.0.inner_mut().try_reserve(decomp.len_utf8())?;
 			decomp.encode_utf8(unsafe { MaybeUninit::slice_assume_init_mut(self.0.spare_capacity_mut()) });
-			unsafe { self.0.0.set_len(self.0.len() + decomp.len_utf8()); }
+			unsafe { self.0.set_len(self.0.len() + decomp.len_utf8()); }
 		}
+
+		Ok(())
 	}
 
 	pub fn pop(&mut self) -> Option<char> {
 		let ch = self.chars().next_back()?;
-		unsafe { self.0.0.set_len(self.0.len() - ch.len_utf8()); }
+		unsafe { self.0.set_len(self.0.len() - ch.len_utf8()); }
 		unsafe { zero(self.0.as_mut_ptr().add(self.0.len()), ch.len_utf8()); }
 		Some(ch)
 	}
@@ -240,6 +474,12 @@ impl PartialEq<Ref<'_>> for RefMut<'_> {
 	}
 }
 
+impl fmt::Debug for RefMut<'_> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_tuple("RefMut").field(&self.0).finish()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -265,4 +505,57 @@ mod tests {
 		assert_eq!(immutable.pop(), Some(' '));
 		assert_eq!(immutable, String::from("Warum Thunfische").borrow());
 	}
+
+	#[test]
+	fn push() {
+		let mut string = String::try_from("Warum").unwrap();
+
+		{
+			let mut mutable = string.borrow_mut();
+			mutable.push(' ');
+			mutable.try_push_str("Thunfische das?").unwrap();
+		}
+
+		assert_eq!(string.borrow(), String::from("Warum Thunfische das?").borrow());
+	}
+
+	#[test]
+	fn budget() {
+		let mut string = String::try_from("Thunfisch").unwrap();
+		string.set_budget(Some(1)).unwrap();
+
+		assert_eq!(string.borrow(), String::from("Thunfisch").borrow());
+		assert!(string.is_wiped());
+		assert!(string.is_empty());
+		assert!(string.try_borrow().is_err());
+	}
+
+	#[test]
+	fn with() {
+		let mut string = String::new();
+
+		string.with_mut(|mutable| mutable.push_str("Warum Thunfische das?"));
+		assert_eq!(string.with(str::len), 21);
+		assert!(string.with(|string| string.starts_with("Warum")));
+	}
+
+	#[test]
+	fn clone() {
+		let string = String::try_from("Thunfisch").unwrap();
+		let mut clone = string.clone();
+
+		clone.borrow_mut().push_str(" das?");
+		assert_eq!(string.borrow(), String::from("Thunfisch").borrow());
+		assert_eq!(clone.borrow(), String::from("Thunfisch das?").borrow());
+	}
+
+	#[test]
+	fn masked() {
+		let string = String::from("Käsekuchen");
+
+		assert_eq!(string.masked(Mask::new().suffix(4)).to_string(), "******chen");
+		assert_eq!(string.masked(Mask::new().prefix(2).fill('#')).to_string(), "Ka\u{308}########");
+		assert!(!format!("{string:?}").contains("chen"));
+		assert!(!format!("{:?}", string.borrow()).contains("chen"));
+	}
 }
@@ -0,0 +1,217 @@
+//! Reader‐writer guard for sharing between threads
+//!
+//! [`Guard::borrow_mut`] requires exclusive access to the guard, so sharing a mutable guard between threads would
+//! otherwise require an additional lock, tracking the lock state separately from the protection state.
+//! [`RwGuard`] combines both in the state word of the guard.
+
+use crate::error::BorrowError;
+use crate::guard::{Guard, Ref, RefMut};
+use crate::stats::Stats;
+use crate::traits::Protectable;
+
+use std::time::{Duration, Instant};
+
+/// Reader‐writer guard
+///
+/// Borrows block while the value is mutably borrowed and mutable borrows block while the value is borrowed at all.
+/// Readers are preferred, so writers may be starved by continuous reading.
+#[must_use]
+#[derive(Debug)]
+pub struct RwGuard<T: Protectable>(Guard<T>);
+
+impl<T: Protectable> RwGuard<T> {
+	#[inline]
+	pub fn new(guard: Guard<T>) -> Self {
+		Self(guard)
+	}
+
+	#[inline]
+	pub fn into_inner(self) -> Guard<T> {
+		self.0
+	}
+
+	#[inline]
+	pub fn get_mut(&mut self) -> &mut Guard<T> {
+		&mut self.0
+	}
+
+	/// Immutably borrow guarded value, blocking while it is mutably borrowed
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn read(&self) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		self.0.try_borrow_until(None)
+	}
+
+	/// Immutably borrow guarded value, failing with [`BorrowError::Borrowed`] if it is mutably borrowed
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_read(&self) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		self.0.try_borrow_until(Some(Instant::now()))
+	}
+
+	/// Immutably borrow guarded value, failing with [`BorrowError::Borrowed`] if it remains mutably borrowed for
+	/// longer than `timeout`
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn read_timeout(&self, timeout: Duration) -> Result<Ref<'_, T>, BorrowError<Ref<'_, T>>> {
+		self.0.try_borrow_until(Some(Instant::now() + timeout))
+	}
+
+	/// Mutably borrow guarded value, blocking while it is borrowed
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn write(&self) -> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		self.0.try_borrow_shared_mut_until(None)
+	}
+
+	/// Mutably borrow guarded value, failing with [`BorrowError::Borrowed`] if it is borrowed
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn try_write(&self) -> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		self.0.try_borrow_shared_mut_until(Some(Instant::now()))
+	}
+
+	/// Mutably borrow guarded value, failing with [`BorrowError::Borrowed`] if it remains borrowed for longer than
+	/// `timeout`
+	#[allow(clippy::missing_errors_doc)] #[inline] #[track_caller]
+	pub fn write_timeout(&self, timeout: Duration) -> Result<RefMut<'_, T>, BorrowError<RefMut<'_, T>>> {
+		self.0.try_borrow_shared_mut_until(Some(Instant::now() + timeout))
+	}
+
+	#[must_use] #[inline]
+	pub fn is_poisoned(&self) -> bool {
+		self.0.is_poisoned()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_wiped(&self) -> bool {
+		self.0.is_wiped()
+	}
+
+	#[inline]
+	pub fn clear_poison(&self) {
+		self.0.clear_poison();
+	}
+
+	#[must_use] #[inline]
+	pub fn stats(&self) -> Option<Stats> {
+		self.0.stats()
+	}
+
+	#[must_use] #[inline]
+	pub fn is_accessible(&self) -> bool {
+		self.0.is_accessible()
+	}
+
+	#[must_use] #[inline]
+	pub fn readers(&self) -> usize {
+		self.0.readers()
+	}
+}
+
+impl<T: Protectable> From<Guard<T>> for RwGuard<T> {
+	#[inline]
+	fn from(guard: Guard<T>) -> Self {
+		Self::new(guard)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::boxed::Box;
+	use crate::vec::Vec;
+
+	#[test]
+	fn exclusion() {
+		let test = RwGuard::new(Box::<u32>::new(0x55555555));
+
+		{
+			let immutable = test.read().unwrap();
+			assert_eq!(test.readers(), 1);
+			assert!(matches!(test.try_write(), Err(BorrowError::Borrowed)));
+			assert!(matches!(test.write_timeout(Duration::from_millis(10)), Err(BorrowError::Borrowed)));
+			assert_eq!(*test.try_read().unwrap(), *immutable);
+		}
+
+		{
+			let mut mutable = test.write().unwrap();
+			*mutable = 0xdeadbeef;
+			assert!(test.is_accessible());
+			assert_eq!(test.readers(), 0);
+			assert!(matches!(test.try_read(), Err(BorrowError::Borrowed)));
+			assert!(matches!(test.read_timeout(Duration::from_millis(10)), Err(BorrowError::Borrowed)));
+			assert!(matches!(test.try_write(), Err(BorrowError::Borrowed)));
+		}
+
+		assert_eq!(*test.read().unwrap(), 0xdeadbeef);
+		assert_eq!(*test.write_timeout(Duration::from_millis(10)).unwrap(), 0xdeadbeef);
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn protection() {
+		use bulletproof::Bulletproof;
+
+		let mut test = RwGuard::new(Box::<u32>::new(0x55555555));
+		let bp = unsafe { Bulletproof::new() };
+
+		let ptr = unsafe { &mut **test.get_mut().inner_mut() } as *mut u32;
+
+		{
+			let _immutable = test.read().unwrap();
+			assert_eq!(unsafe { bp.store(ptr, &0x55) }, Err(()));
+		}
+
+		{
+			let _mutable = test.write().unwrap();
+			assert_eq!(unsafe { bp.store(ptr, &0xdeadbeef) }, Ok(()));
+		}
+
+		assert_eq!(unsafe { bp.load(ptr) }, Err(()));
+		assert_eq!(*test.read().unwrap(), 0xdeadbeef);
+	}
+
+	#[test]
+	fn concurrent() {
+		use std::cmp::max;
+		use std::sync::{Arc, Barrier};
+		use std::thread;
+
+		const LIMIT: usize = 1024;
+
+		let test = Arc::new(RwGuard::new(Vec::<usize>::new()));
+		let concurrency = max(4, thread::available_parallelism().unwrap().get());
+		let barrier = Arc::new(Barrier::new(2 * concurrency));
+		let mut threads = std::vec::Vec::with_capacity(2 * concurrency);
+
+		for _ in 0..concurrency {
+			let start = barrier.clone();
+			let writer = test.clone();
+
+			threads.push(thread::spawn(move || {
+				start.wait();
+
+				for i in 0..LIMIT {
+					writer.write().unwrap().push(i);
+				}
+			}));
+
+			let start = barrier.clone();
+			let reader = test.clone();
+
+			threads.push(thread::spawn(move || {
+				start.wait();
+
+				for _ in 0..LIMIT {
+					let immutable = reader.read().unwrap();
+					assert!(immutable.iter().all(|&elem| elem < LIMIT));
+				}
+			}));
+		}
+
+		for thread in threads {
+			thread.join().unwrap();
+		}
+
+		let mut test = Arc::into_inner(test).unwrap().into_inner();
+		assert_eq!(test.len(), concurrency * LIMIT);
+		assert!(!test.is_poisoned());
+		drop(test.borrow_mut());
+	}
+}
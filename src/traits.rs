@@ -6,10 +6,20 @@ use std::io::Error;
 
 pub trait AsPages {
 	fn as_pages(&self) -> Option<Pages>;
+
+	/// Pages including adjacent guard pages
+	fn as_outer_pages(&self) -> Option<Pages<'_>> {
+		self.as_pages()
+	}
 }
 
 pub trait Protectable {
 	fn lock(&self) -> Result<(), Error>;
 	fn unlock(&self) -> Result<(), Error>;
 	fn unlock_mut(&mut self) -> Result<(), Error>;
+
+	/// Make permanently read‐only, returning whether protection changes have been prevented
+	fn seal(&mut self) -> Result<bool, Error> {
+		Ok(false)
+	}
 }
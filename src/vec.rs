@@ -3,18 +3,51 @@
 use crate::auxiliary::zero;
 use crate::pages::{Pages, Allocation, GuardedAlloc};
 use crate::alloc::Sensitive;
+use crate::error::Error;
 use crate::guard::{Guard, Ref, RefMut};
+use crate::redact::{Mask, Masked};
 use crate::traits::{AsPages, Protectable};
 
+use std::clone::CloneToUninit;
 use std::cmp::{PartialEq, min, max};
 use std::default::Default;
-use std::mem::MaybeUninit;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::RangeBounds;
 
 pub(crate) type InnerVec<T> = std::vec::Vec<T, Sensitive>;
 
 /// Guarded [vector](std::vec::Vec) type
 pub type Vec<T> = Guard<InnerVec<T>>;
 
+/// Draining iterator, see [`RefMut::drain`]
+///
+/// Elements vacated by the drained range are wiped when the iterator is dropped.
+pub struct Drain<'t, T> {
+	drain: ManuallyDrop<std::vec::Drain<'t, T, Sensitive>>,
+	vec: *mut InnerVec<T>,
+	len: usize,
+}
+
+/// Splicing iterator, see [`RefMut::splice`]
+///
+/// Elements vacated by the spliced range are wiped when the iterator is dropped.
+pub struct Splice<'t, I: Iterator> {
+	splice: ManuallyDrop<std::vec::Splice<'t, I, Sensitive>>,
+	vec: *mut InnerVec<I::Item>,
+	len: usize,
+}
+
+/// Wipe elements between the length of `vec` and `len`, which were vacated by shrinking or moving elements
+fn wipe_vacated<T>(vec: &mut InnerVec<T>, len: usize) {
+	let len = min(len, vec.capacity());
+
+	if len > vec.len() {
+		unsafe { zero(vec.as_mut_ptr().add(vec.len()), len - vec.len()); }
+	}
+}
+
 impl<T> AsPages for InnerVec<T> {
 	fn as_pages(&self) -> Option<Pages> {
 		if self.capacity() > 0 {
@@ -23,6 +56,14 @@ impl<T> AsPages for InnerVec<T> {
 			None
 		}
 	}
+
+	fn as_outer_pages(&self) -> Option<Pages<'_>> {
+		if self.capacity() > 0 {
+			Some(unsafe { GuardedAlloc::<{ Sensitive::GUARD_PAGES }>::from_ptr(self.as_ptr() as *mut T, self.capacity() * std::mem::size_of::<T>()).into_outer_pages() })
+		} else {
+			None
+		}
+	}
 }
 
 impl<T> Vec<T> {
@@ -43,7 +84,11 @@ impl<T> Vec<T> {
 	}
 
 	pub fn new() -> Self {
-		let guard = Guard::from_inner(std::vec::Vec::new_in(Sensitive));
+		Self::new_in(Sensitive)
+	}
+
+	pub fn new_in(alloc: Sensitive) -> Self {
+		let guard = Guard::from_inner(std::vec::Vec::new_in(alloc));
 		debug_assert!(guard.capacity() == 0);
 		guard
 	}
@@ -52,54 +97,233 @@ impl<T> Vec<T> {
 		Guard::from_inner(std::vec::Vec::with_capacity_in(Allocation::align(capacity), Sensitive))
 	}
 
-	pub fn with_capacity(capacity: usize) -> Self {
-		let mut guard = Self::with_capacity_unprotected(capacity);
+	pub(crate) fn try_with_capacity_unprotected_in(capacity: usize, alloc: Sensitive) -> Result<Self, Error> {
+		Ok(Guard::from_inner(std::vec::Vec::try_with_capacity_in(Allocation::align(capacity), alloc)?))
+	}
+
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_with_capacity_in(capacity: usize, alloc: Sensitive) -> Result<Self, Error> {
+		let mut guard = Self::try_with_capacity_unprotected_in(capacity, alloc)?;
+		guard.mutate(|vec| vec.lock())?;
+		Ok(guard)
+	}
+
+	#[allow(clippy::missing_errors_doc)] #[inline]
+	pub fn try_with_capacity(capacity: usize) -> Result<Self, Error> {
+		Self::try_with_capacity_in(capacity, Sensitive)
+	}
+
+	pub fn with_capacity_in(capacity: usize, alloc: Sensitive) -> Self {
+		let mut guard = Guard::from_inner(std::vec::Vec::with_capacity_in(Allocation::align(capacity), alloc));
 		guard.mutate(|vec| vec.lock().unwrap());
 		guard
 	}
 
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self::with_capacity_in(capacity, Sensitive)
+	}
+
+	/// Allocator of the vector, or the untagged allocator once wiped
+	#[inline]
+	pub fn allocator(&self) -> &Sensitive {
+		if self.is_wiped() { &Sensitive } else { unsafe { self.inner() }.allocator() }
+	}
+
 	#[inline]
 	pub fn capacity(&self) -> usize {
-		unsafe { self.inner().capacity() }
+		if self.is_wiped() { 0 } else { unsafe { self.inner().capacity() } }
 	}
 
-	pub fn reserve(&mut self, capacity: usize) {
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_reserve(&mut self, capacity: usize) -> Result<(), Error> {
+		if self.is_wiped() {
+			return Err(Error::Wiped);
+		}
+
+		if self.is_frozen() {
+			return Err(Error::Frozen);
+		}
+
 		self.mutate(|vec| {
-			vec.reserve(capacity);
-			vec.lock().unwrap();
-		});
+			// Reallocation requires access to the current contents
+			vec.unlock_mut()?;
+			let result = vec.try_reserve(capacity);
+			vec.lock()?;
+			Ok(result?)
+		})
 	}
 
-	pub fn reserve_exact(&mut self, capacity: usize) {
+	#[allow(clippy::missing_errors_doc)]
+	pub fn try_reserve_exact(&mut self, capacity: usize) -> Result<(), Error> {
+		if self.is_wiped() {
+			return Err(Error::Wiped);
+		}
+
+		if self.is_frozen() {
+			return Err(Error::Frozen);
+		}
+
 		self.mutate(|vec| {
-			vec.reserve_exact(capacity);
-			vec.lock().unwrap();
-		});
+			// Reallocation requires access to the current contents
+			vec.unlock_mut()?;
+			let result = vec.try_reserve_exact(capacity);
+			vec.lock()?;
+			Ok(result?)
+		})
+	}
+
+	pub fn reserve(&mut self, capacity: usize) {
+		self.try_reserve(capacity).unwrap();
+	}
+
+	pub fn reserve_exact(&mut self, capacity: usize) {
+		self.try_reserve_exact(capacity).unwrap();
 	}
 
 	#[inline]
 	pub fn len(&self) -> usize {
-		unsafe { self.inner().len() }
+		if self.is_wiped() { 0 } else { unsafe { self.inner().len() } }
 	}
 
 	#[inline]
 	pub fn is_empty(&self) -> bool {
-		unsafe { self.inner().is_empty() }
+		self.is_wiped() || unsafe { self.inner().is_empty() }
 	}
 
+	/// Force the length of the vector to `len`
+	///
+	/// # Safety
+	///
+	/// See [`std::vec::Vec::set_len`].
 	#[inline]
 	pub unsafe fn set_len(&mut self, len: usize) {
 		self.inner_mut().set_len(len);
 	}
 
+	/// Pointer to the elements, dangling once wiped
 	#[inline]
 	pub fn as_ptr(&self) -> *const T {
-		unsafe { self.inner() }.as_ptr()
+		if self.is_wiped() { std::ptr::dangling() } else { unsafe { self.inner() }.as_ptr() }
 	}
 
+	/// Mutable pointer to the elements, dangling once wiped
 	#[inline]
 	pub fn as_mut_ptr(&mut self) -> *mut T {
-		unsafe { self.inner_mut() }.as_mut_ptr()
+		if self.is_wiped() { std::ptr::dangling_mut() } else { unsafe { self.inner_mut() }.as_mut_ptr() }
+	}
+
+	/// Move elements to the end of `dest`, wiping and freeing the guarded allocation
+	///
+	/// The elements are copied directly into `dest`, which is not protected.
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn declassify_into(self, dest: &mut std::vec::Vec<T>) -> Result<(), Error> {
+		dest.try_reserve(self.len())?;
+
+		let mut vec = self.into_unguarded()?;
+		let len = vec.len();
+
+		unsafe {
+			dest.as_mut_ptr().add(dest.len()).copy_from_nonoverlapping(vec.as_ptr(), len);
+			dest.set_len(dest.len() + len);
+
+			// Elements have been moved out, so only zero and free their memory
+			vec.set_len(0);
+		}
+
+		Ok(())
+	}
+
+	/// Move elements into a [standard vector](std::vec::Vec), wiping and freeing the guarded allocation
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn into_inner(self) -> Result<std::vec::Vec<T>, Error> {
+		let mut dest = std::vec::Vec::new();
+		self.declassify_into(&mut dest)?;
+		Ok(dest)
+	}
+
+	/// Take elements, leaving an empty vector in their place
+	///
+	/// The elements are not copied; their guarded allocation is moved to the returned guard.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn take(&mut self) -> Result<Self, Error> {
+		self.check_idle()?;
+
+		let mut taken = Self::new_in(*self.allocator());
+		self.swap(&mut taken)?;
+		Ok(taken)
+	}
+}
+
+impl<T: Copy> Vec<T> {
+	/// Replace elements with those copied from `source`, wiping the previous elements and clearing out `source`
+	///
+	/// As `source` is zeroed in place, its elements must be [`Copy`], so that no destructor runs on zeroed values.
+	#[allow(clippy::missing_errors_doc)]
+	pub fn replace(&mut self, source: &mut [T]) -> Result<(), Error> {
+		// Fail before the source is consumed
+		self.check_idle()?;
+
+		let len = source.len();
+		let mut guard = Self::try_with_capacity_unprotected_in(len, *self.allocator())?;
+
+		unsafe {
+			guard.as_mut_ptr().copy_from_nonoverlapping(source.as_ptr(), len);
+			guard.set_len(len);
+			zero(source.as_mut_ptr(), len);
+		}
+
+		guard.mutate(|vec| vec.lock())?;
+		self.replace_with(guard)
+	}
+}
+
+impl<T: Clone> Vec<T> {
+	/// Clone into a new guarded allocation
+	///
+	/// The elements are cloned directly from the source allocation, which is immutably borrowed for the duration of
+	/// the copy, into the new allocation, which is protected once the copy is complete.
+	///
+	/// The borrow for cloning is taken from the [access budget](Guard::set_budget) first. The remaining budget is then
+	/// split between the original and the clone, which receives the smaller half, so that cloning does not add up to
+	/// more accesses than the original had left. Fails with [`Error::Exhausted`] if fewer than two accesses remain.
+	#[allow(clippy::missing_errors_doc)] #[track_caller]
+	pub fn try_clone(&self) -> Result<Self, Error> {
+		let source = self.try_borrow()?;
+		let mut guard = Self::try_with_capacity_unprotected_in(source.len(), *source.inner().allocator())?;
+
+		unsafe {
+			source.as_slice().clone_to_uninit(guard.as_mut_ptr().cast());
+			guard.set_len(source.len());
+		}
+
+		guard.mutate(|vec| vec.lock())?;
+		guard.set_budget(self.split_budget()?)?;
+		Ok(guard)
+	}
+}
+
+impl<T: Clone> Clone for Vec<T> {
+	#[track_caller]
+	fn clone(&self) -> Self {
+		self.try_clone().unwrap()
+	}
+}
+
+impl Vec<u8> {
+	/// Format bytes as hexadecimal, revealing only what `mask` permits
+	#[inline]
+	pub fn masked(&self, mask: Mask) -> Masked<'_, Self> {
+		Masked { guard: self, mask }
+	}
+}
+
+impl fmt::Display for Masked<'_, Vec<u8>> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.guard.try_borrow() {
+			Ok(vec) => self.mask.write_bytes(fmt, vec.as_slice()),
+			Err(_) => fmt.write_str("<redacted>"),
+		}
 	}
 }
 
@@ -214,6 +438,16 @@ impl<T> RefMut<'_, InnerVec<T>> {
 		unsafe { self.0.inner() }.len()
 	}
 
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		unsafe { self.0.inner() }.is_empty()
+	}
+
+	#[inline]
+	pub fn capacity(&self) -> usize {
+		unsafe { self.0.inner() }.capacity()
+	}
+
 	#[inline]
 	pub fn push(&mut self, value: T) {
 		self.inner_mut().push(value);
@@ -221,7 +455,102 @@ impl<T> RefMut<'_, InnerVec<T>> {
 
 	#[inline]
 	pub fn pop(&mut self) -> Option<T> {
-		self.inner_mut().pop()
+		let len = self.len();
+		let value = self.inner_mut().pop();
+		wipe_vacated(self.inner_mut(), len);
+		value
+	}
+
+	#[inline]
+	pub fn insert(&mut self, index: usize, value: T) {
+		self.inner_mut().insert(index, value);
+	}
+
+	#[inline]
+	pub fn remove(&mut self, index: usize) -> T {
+		let len = self.len();
+		let value = self.inner_mut().remove(index);
+		wipe_vacated(self.inner_mut(), len);
+		value
+	}
+
+	#[inline]
+	pub fn swap_remove(&mut self, index: usize) -> T {
+		let len = self.len();
+		let value = self.inner_mut().swap_remove(index);
+		wipe_vacated(self.inner_mut(), len);
+		value
+	}
+
+	#[inline]
+	pub fn truncate(&mut self, len: usize) {
+		let old = self.len();
+		self.inner_mut().truncate(len);
+		wipe_vacated(self.inner_mut(), old);
+	}
+
+	#[inline]
+	pub fn clear(&mut self) {
+		self.truncate(0);
+	}
+
+	/// Remove `range`, returning the removed elements as an iterator
+	///
+	/// Vacated elements are wiped when the iterator is dropped. If it is leaked, the elements past the start of
+	/// `range` are leaked as well and not wiped.
+	pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
+		where R: RangeBounds<usize> {
+		let len = self.len();
+		let vec: *mut InnerVec<T> = self.inner_mut();
+
+		Drain { drain: ManuallyDrop::new(unsafe { &mut *vec }.drain(range)), vec, len }
+	}
+
+	pub fn retain<F>(&mut self, keep: F)
+		where F: FnMut(&T) -> bool {
+		let len = self.len();
+		self.inner_mut().retain(keep);
+		wipe_vacated(self.inner_mut(), len);
+	}
+
+	/// Split off the elements from `at` into a new guarded allocation
+	///
+	/// # Panics
+	///
+	/// Panics if `at > len` or if the new allocation fails.
+	pub fn split_off(&mut self, at: usize) -> Vec<T> {
+		let len = self.len();
+		assert!(at <= len, "`at` split index (is {at}) should be <= len (is {len})");
+
+		let mut tail = Vec::<T>::try_with_capacity_unprotected_in(len - at, *self.inner().allocator()).unwrap();
+
+		unsafe {
+			tail.as_mut_ptr().copy_from_nonoverlapping(self.as_ptr().add(at), len - at);
+			tail.set_len(len - at);
+			self.inner_mut().set_len(at);
+		}
+
+		wipe_vacated(self.inner_mut(), len);
+		tail.mutate(|vec| vec.lock()).unwrap();
+		tail
+	}
+
+	/// Move all elements of `other` to the end, wiping them in `other`
+	pub fn append(&mut self, other: &mut RefMut<'_, InnerVec<T>>) {
+		let len = other.len();
+		self.inner_mut().append(other.inner_mut());
+		wipe_vacated(other.inner_mut(), len);
+	}
+
+	/// Replace `range` with the elements of `replace_with`, returning the removed elements as an iterator
+	///
+	/// Vacated elements are wiped when the iterator is dropped.
+	pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter>
+		where R: RangeBounds<usize>, I: IntoIterator<Item = T> {
+		let len = self.len();
+		let vec: *mut InnerVec<T> = self.inner_mut();
+
+		Splice { splice: ManuallyDrop::new(unsafe { &mut *vec }.splice(range, replace_with)), vec, len }
 	}
 
 	#[inline]
@@ -250,6 +579,11 @@ impl<T> RefMut<'_, InnerVec<T>> {
 		self.inner_mut().reserve_exact(capacity);
 	}
 
+	/// Force the length of the vector to `len`
+	///
+	/// # Safety
+	///
+	/// See [`std::vec::Vec::set_len`].
 	#[inline]
 	pub unsafe fn set_len(&mut self, len: usize) {
 		self.inner_mut().set_len(len);
@@ -259,7 +593,101 @@ impl<T> RefMut<'_, InnerVec<T>> {
 impl<T: Clone> RefMut<'_, InnerVec<T>> {
 	#[inline]
 	pub fn resize(&mut self, len: usize, value: T) {
+		let old = self.len();
 		self.inner_mut().resize(len, value);
+		wipe_vacated(self.inner_mut(), old);
+	}
+
+	#[inline]
+	pub fn extend_from_slice(&mut self, other: &[T]) {
+		self.inner_mut().extend_from_slice(other);
+	}
+}
+
+impl<T: PartialEq> RefMut<'_, InnerVec<T>> {
+	pub fn dedup(&mut self) {
+		let len = self.len();
+		self.inner_mut().dedup();
+		wipe_vacated(self.inner_mut(), len);
+	}
+}
+
+impl<T> Iterator for Drain<'_, T> {
+	type Item = T;
+
+	#[inline]
+	fn next(&mut self) -> Option<T> {
+		self.drain.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.drain.size_hint()
+	}
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+	#[inline]
+	fn next_back(&mut self) -> Option<T> {
+		self.drain.next_back()
+	}
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+	fn drop(&mut self) {
+		unsafe {
+			// Moves the tail back and resets the length
+			ManuallyDrop::drop(&mut self.drain);
+			wipe_vacated(&mut *self.vec, self.len);
+		}
+	}
+}
+
+impl<T> fmt::Debug for Drain<'_, T> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_struct("Drain").finish_non_exhaustive()
+	}
+}
+
+impl<I: Iterator> Iterator for Splice<'_, I> {
+	type Item = I::Item;
+
+	#[inline]
+	fn next(&mut self) -> Option<I::Item> {
+		self.splice.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.splice.size_hint()
+	}
+}
+
+impl<I: Iterator> DoubleEndedIterator for Splice<'_, I> {
+	#[inline]
+	fn next_back(&mut self) -> Option<I::Item> {
+		self.splice.next_back()
+	}
+}
+
+impl<I: Iterator> ExactSizeIterator for Splice<'_, I> {}
+
+impl<I: Iterator> Drop for Splice<'_, I> {
+	fn drop(&mut self) {
+		unsafe {
+			// Inserts the replacement, moving the tail as necessary
+			ManuallyDrop::drop(&mut self.splice);
+			wipe_vacated(&mut *self.vec, self.len);
+		}
+	}
+}
+
+impl<I: Iterator> fmt::Debug for Splice<'_, I> {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.debug_struct("Splice").finish_non_exhaustive()
 	}
 }
 
@@ -294,6 +722,7 @@ impl PartialEq<&str> for RefMut<'_, InnerVec<u8>> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::error::BorrowError;
 
 	#[cfg(target_os = "linux")]
 	#[test]
@@ -373,6 +802,204 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn reserve() {
+		let mut test = Vec::<u8>::try_with_capacity(1).unwrap();
+		test.borrow_mut().push(0x55);
+
+		test.try_reserve(2 * test.capacity()).unwrap();
+		assert_eq!(test.borrow()[0], 0x55);
+
+		test.reserve_exact(2 * test.capacity());
+		assert_eq!(test.borrow()[0], 0x55);
+
+		assert!(test.try_reserve(usize::MAX).is_err());
+	}
+
+	#[test]
+	fn mutation() {
+		fn vacated(vec: &RefMut<'_, InnerVec<u8>>) -> bool {
+			unsafe { std::slice::from_raw_parts(vec.as_ptr().add(vec.len()), vec.capacity() - vec.len()) }.iter().all(|&byte| byte == 0)
+		}
+
+		let mut test = Vec::<u8>::with_capacity(64);
+		let mut other = Vec::<u8>::from(vec![0xcc; 4]);
+		let mut mutable = test.borrow_mut();
+
+		mutable.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+		mutable.insert(0, 0);
+		assert_eq!((mutable.remove(7), mutable.swap_remove(1)), (7, 1));
+		assert!(mutable == [0u8, 8, 2, 3, 4, 5, 6] && vacated(&mutable));
+
+		assert_eq!(mutable.drain(1..3).collect::<std::vec::Vec<_>>(), [8, 2]);
+		assert!(mutable == [0u8, 3, 4, 5, 6] && vacated(&mutable));
+
+		assert_eq!(mutable.splice(..2, [9]).collect::<std::vec::Vec<_>>(), [0, 3]);
+		assert!(mutable == [9u8, 4, 5, 6] && vacated(&mutable));
+
+		mutable.retain(|&value| value % 2 == 0);
+		assert!(mutable == [4u8, 6] && vacated(&mutable));
+
+		{
+			let mut other = other.borrow_mut();
+			mutable.append(&mut other);
+			assert!(other.is_empty() && vacated(&other));
+		}
+
+		mutable.dedup();
+		assert!(mutable == [4u8, 6, 0xcc] && vacated(&mutable));
+
+		let tail = mutable.split_off(1);
+		assert!(mutable == [4u8] && vacated(&mutable));
+
+		mutable.truncate(0);
+		assert!(mutable.is_empty() && vacated(&mutable));
+		drop(mutable);
+
+		assert!(*tail.borrow() == [6u8, 0xcc]);
+	}
+
+	#[test]
+	fn tagged() {
+		use crate::alloc::Tag;
+
+		static TAG: Tag = Tag::new("vec", 1048576);
+
+		{
+			let mut test = Vec::<u8>::new_in(Sensitive::tagged(&TAG));
+			assert_eq!(TAG.usage(), 0);
+
+			test.borrow_mut().push(0x55);
+			assert!(TAG.usage() > 0);
+
+			assert!(test.try_reserve(1048576).is_err());
+			assert_eq!(test.borrow()[0], 0x55);
+		}
+
+		assert_eq!(TAG.usage(), 0);
+	}
+
+	#[test]
+	fn budget() {
+		use crate::alloc::Tag;
+
+		static TAG: Tag = Tag::unlimited("budget");
+
+		let mut test = Vec::<u8>::new_in(Sensitive::tagged(&TAG));
+		test.borrow_mut().resize(64, 0x55);
+		test.set_budget(Some(3)).unwrap();
+
+		assert_eq!(test.borrow()[0], 0x55);
+		test.borrow_mut()[0] = 0xff;
+		assert!(TAG.usage() > 0);
+
+		assert_eq!(test.borrow()[0], 0xff);
+		assert!(test.is_wiped());
+		assert_eq!(TAG.usage(), 0);
+		assert!(matches!(test.try_borrow(), Err(BorrowError::Wiped)));
+		assert!(test.allocator().tag().is_none());
+		assert_eq!(test.as_ptr(), std::ptr::dangling());
+		assert_eq!(test.as_mut_ptr(), std::ptr::dangling_mut());
+	}
+
+	#[test]
+	fn masked() {
+		let mut test = Vec::<u8>::from(vec![0xde, 0xad, 0xbe, 0xef]);
+		assert_eq!(test.masked(Mask::new().prefix(1).suffix(1)).to_string(), "de****ef");
+
+		test.set_budget(Some(0)).unwrap();
+		assert_eq!(test.masked(Mask::new()).to_string(), "<redacted>");
+	}
+
+	#[test]
+	fn freeze() {
+		let mut test = Vec::<u8>::from(vec![0x55; 64]);
+		test.freeze().unwrap();
+
+		assert_eq!(test.borrow()[..], [0x55; 64]);
+		assert!(matches!(test.try_borrow_mut(), Err(BorrowError::Frozen)));
+		assert!(matches!(test.try_reserve(128), Err(Error::Frozen)));
+		assert!(matches!(test.take(), Err(Error::Frozen)));
+	}
+
+	#[test]
+	fn declassify() {
+		let test = Vec::<u8>::from(vec![0x55; 64]);
+		assert_eq!(test.into_inner().unwrap(), [0x55; 64]);
+
+		let mut dest = vec![0xaa];
+		Vec::<u8>::from(vec![0x55; 3]).declassify_into(&mut dest).unwrap();
+		assert_eq!(dest, [0xaa, 0x55, 0x55, 0x55]);
+	}
+
+	#[test]
+	fn replace() {
+		let mut test = Vec::<u8>::from(vec![0x55; 64]);
+		let mut source = [0xaa; 16];
+
+		test.replace(&mut source).unwrap();
+		assert_eq!(source, [0; 16]);
+		assert_eq!(test.borrow()[..], [0xaa; 16]);
+
+		let mut taken = test.take().unwrap();
+		assert!(test.is_empty());
+		assert_eq!(taken.len(), 16);
+
+		test.swap(&mut taken).unwrap();
+		assert_eq!((test.len(), taken.len()), (16, 0));
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let mut vec = test.borrow_mut();
+			vec.push(0xff);
+			panic!();
+		})).is_err());
+
+		assert!(matches!(test.take(), Err(Error::Poisoned)));
+		let mut source = [0x55; 4];
+		assert!(matches!(test.replace(&mut source), Err(Error::Poisoned)));
+		assert_eq!(source, [0x55; 4]);
+		assert!(matches!(taken.swap(&mut test), Err(Error::Poisoned)));
+	}
+
+	#[test]
+	fn clone() {
+		let mut test = Vec::<u8>::from(vec![0x55; 64]);
+		let mut clone = test.clone();
+
+		assert_eq!(clone.len(), 64);
+		assert!(clone.capacity() >= 64);
+
+		clone.borrow_mut().push(0xff);
+		assert_eq!(test.len(), 64);
+		assert_eq!(clone.borrow()[..], [[0x55; 64].as_slice(), &[0xff]].concat()[..]);
+
+		test.borrow_mut().pop();
+		assert_eq!(test.clone().len(), 63);
+		assert_eq!(Vec::<u8>::new().clone().len(), 0);
+
+		test.set_budget(Some(5)).unwrap();
+		assert_eq!(test.clone().budget(), Some(2));
+		assert_eq!(test.budget(), Some(2));
+	}
+
+	#[test]
+	fn poison_wipe() {
+		let mut test = Vec::<u8>::from(vec![0x55; 64]);
+		test.set_wipe_on_poison(true);
+
+		assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			let mut mutable = test.borrow_mut();
+			mutable[0] = 0xff;
+			panic!();
+		})).is_err());
+
+		assert!(test.is_wiped());
+		assert_eq!(test.len(), 0);
+		assert_eq!(test.capacity(), 0);
+		assert!(test.try_borrow().is_err());
+		assert!(test.try_reserve(1).is_err());
+	}
+
 	#[test]
 	fn eq() {
 		assert_eq!(Vec::<u8>::from(vec![]), [] as [u8; 0]);
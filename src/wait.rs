@@ -0,0 +1,111 @@
+//! Waiting on atomic state changes
+//!
+//! On Linux, this uses [`futex(2)`](https://man7.org/linux/man-pages/man2/futex.2.html) on the most significant 32
+//! bits of the state, and on Windows `WaitOnAddress` likewise. Other platforms still fall back to yielding the current
+//! time slice, so that waiters spin there.
+
+use std::sync::atomic::AtomicUsize;
+use std::time::Duration;
+
+#[cfg(any(target_os = "linux", target_os = "android", windows))]
+fn word(state: &AtomicUsize) -> *const u32 {
+	let ptr = state.as_ptr().cast::<u32>();
+
+	if cfg!(all(target_pointer_width = "64", target_endian = "little")) {
+		unsafe { ptr.add(1) }
+	} else {
+		ptr
+	}
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", windows))]
+fn high(state: usize) -> u32 {
+	(state >> (usize::BITS - u32::BITS)) as u32
+}
+
+/// Block until the most significant 32 bits of `state` differ from those of `expected`
+///
+/// May return spuriously.
+pub fn wait(state: &AtomicUsize, expected: usize) {
+	#[cfg(any(target_os = "linux", target_os = "android"))] {
+		use libc::{syscall, SYS_futex, FUTEX_WAIT, FUTEX_PRIVATE_FLAG, timespec};
+
+		unsafe {
+			syscall(SYS_futex, word(state), FUTEX_WAIT | FUTEX_PRIVATE_FLAG, high(expected), std::ptr::null::<timespec>());
+		}
+	}
+
+	#[cfg(windows)] {
+		use winapi::um::synchapi::WaitOnAddress;
+		use winapi::um::winbase::INFINITE;
+
+		let expected = high(expected);
+
+		unsafe {
+			WaitOnAddress(word(state).cast_mut().cast(), (&raw const expected).cast_mut().cast(), 4, INFINITE);
+		}
+	}
+
+	#[cfg(not(any(target_os = "linux", target_os = "android", windows)))] {
+		let _ = (state, expected);
+		std::thread::yield_now();
+	}
+}
+
+/// Block until the most significant 32 bits of `state` differ from those of `expected` or `timeout` has elapsed
+///
+/// May return spuriously.
+pub fn wait_timeout(state: &AtomicUsize, expected: usize, timeout: Duration) {
+	#[cfg(any(target_os = "linux", target_os = "android"))] {
+		use libc::{syscall, SYS_futex, FUTEX_WAIT, FUTEX_PRIVATE_FLAG, timespec, time_t, c_long};
+
+		let timeout = timespec {
+			tv_sec: time_t::try_from(timeout.as_secs()).unwrap_or(time_t::MAX),
+			tv_nsec: timeout.subsec_nanos() as c_long,
+		};
+
+		unsafe {
+			syscall(SYS_futex, word(state), FUTEX_WAIT | FUTEX_PRIVATE_FLAG, high(expected), &raw const timeout);
+		}
+	}
+
+	#[cfg(windows)] {
+		use winapi::um::synchapi::WaitOnAddress;
+		use winapi::um::winbase::INFINITE;
+
+		let expected = high(expected);
+		let timeout = u32::try_from(timeout.as_nanos().div_ceil(1_000_000)).unwrap_or(INFINITE - 1).min(INFINITE - 1);
+
+		unsafe {
+			WaitOnAddress(word(state).cast_mut().cast(), (&raw const expected).cast_mut().cast(), 4, timeout);
+		}
+	}
+
+	#[cfg(not(any(target_os = "linux", target_os = "android", windows)))] {
+		let _ = (state, expected, timeout);
+		std::thread::yield_now();
+	}
+}
+
+/// Wake all threads waiting on `state`
+pub fn wake(state: &AtomicUsize) {
+	#[cfg(any(target_os = "linux", target_os = "android"))] {
+		use libc::{syscall, SYS_futex, FUTEX_WAKE, FUTEX_PRIVATE_FLAG, c_int};
+
+		unsafe {
+			syscall(SYS_futex, word(state), FUTEX_WAKE | FUTEX_PRIVATE_FLAG, c_int::MAX);
+		}
+	}
+
+	#[cfg(windows)] {
+		use winapi::um::synchapi::WakeByAddressAll;
+
+		unsafe {
+			WakeByAddressAll(word(state).cast_mut().cast());
+		}
+	}
+
+	#[cfg(not(any(target_os = "linux", target_os = "android", windows)))] {
+		let _ = state;
+	}
+}